@@ -0,0 +1,76 @@
+//! Compares the overhead of calling methods generated through `async_trait` with calling methods generated in `native` mode.
+
+use bytes::Bytes;
+use criterion::{criterion_group, criterion_main, Criterion};
+use jsonrpc_client::{Transport, Url};
+use std::convert::Infallible;
+
+#[jsonrpc_client::api]
+pub trait Math {
+    async fn subtract(&self, subtrahend: i64, minuend: i64) -> i64;
+}
+
+#[jsonrpc_client::api(native)]
+pub trait MathNative {
+    async fn subtract(&self, subtrahend: i64, minuend: i64) -> i64;
+}
+
+/// A transport that answers immediately, so only the overhead of the call itself is measured.
+struct Immediate;
+
+#[derive(Debug)]
+struct Never(Infallible);
+
+impl std::fmt::Display for Never {
+    fn fmt(&self, _: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {}
+    }
+}
+
+impl std::error::Error for Never {}
+
+#[async_trait::async_trait]
+impl Transport for Immediate {
+    type Error = Never;
+
+    async fn send(&self, _: &Url, _: Bytes) -> Result<Bytes, Self::Error> {
+        Ok(Bytes::from_static(
+            br#"{"jsonrpc":"2.0","id":0,"result":1}"#,
+        ))
+    }
+}
+
+#[jsonrpc_client::implement(Math)]
+#[jsonrpc_client::implement(MathNative)]
+struct Client {
+    inner: Immediate,
+    base_url: Url,
+}
+
+fn calls(c: &mut Criterion) {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let client = Client {
+        inner: Immediate,
+        base_url: "http://localhost".parse().unwrap(),
+    };
+
+    let mut group = c.benchmark_group("calls");
+
+    group.bench_function("async_trait", |b| {
+        b.iter(|| runtime.block_on(Math::subtract(&client, 5, 4)).unwrap())
+    });
+    group.bench_function("native", |b| {
+        b.iter(|| {
+            runtime
+                .block_on(MathNative::subtract(&client, 5, 4))
+                .unwrap()
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, calls);
+criterion_main!(benches);
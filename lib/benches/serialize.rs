@@ -0,0 +1,45 @@
+//! Compares serializing requests through `Request`, which converts every argument into a `serde_json::Value` first, with serializing them straight into a buffer through `RequestWriter`.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use jsonrpc_client::{Request, RequestWriter};
+
+/// Builds a hex-encoded blob of the given size, like a raw transaction.
+fn blob(size: usize) -> String {
+    (0..size).map(|i| format!("{:02x}", i % 256)).collect()
+}
+
+fn serialize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("serialize");
+
+    for size in [32, 1024, 100 * 1024] {
+        let hex = blob(size);
+        group.throughput(Throughput::Bytes(hex.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("params", size), &hex, |b, hex| {
+            b.iter(|| {
+                Request::new_v2("sendrawtransaction")
+                    .with_argument("hexstring".to_owned(), hex)
+                    .unwrap()
+                    .with_argument("maxfeerate".to_owned(), 0.1)
+                    .unwrap()
+                    .serialize()
+                    .unwrap()
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("writer", size), &hex, |b, hex| {
+            b.iter(|| {
+                RequestWriter::new_v2("sendrawtransaction")
+                    .with_argument("hexstring", hex)
+                    .unwrap()
+                    .with_argument("maxfeerate", &0.1)
+                    .unwrap()
+                    .finish()
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, serialize);
+criterion_main!(benches);
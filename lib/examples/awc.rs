@@ -0,0 +1,33 @@
+use std::error::Error;
+
+// the errors of awc are not `Send`, hence they don't fit into `anyhow::Error`
+type Result<T> = std::result::Result<T, Box<dyn Error>>;
+
+#[jsonrpc_client::api(local)]
+pub trait Math {
+    async fn subtract(&self, subtrahend: i64, minuend: i64) -> i64;
+}
+
+#[jsonrpc_client::implement_local(MathLocal)]
+struct Client {
+    inner: awc::Client,
+    base_url: jsonrpc_client::Url,
+}
+
+impl Client {
+    fn new(base_url: String) -> Result<Self> {
+        Ok(Self {
+            inner: awc::Client::default(),
+            base_url: base_url.parse()?,
+        })
+    }
+}
+
+#[actix_rt::main]
+async fn main() -> Result<()> {
+    let client = Client::new("http://example-jsonrpc.org/".to_owned())?;
+
+    let _ = client.subtract(10, 5).await?;
+
+    Ok(())
+}
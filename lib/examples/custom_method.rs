@@ -1,5 +1,5 @@
 use anyhow::Result;
-use jsonrpc_client::SendRequest;
+use jsonrpc_client::{Response, Transport};
 
 #[jsonrpc_client::api]
 pub trait Math {
@@ -30,11 +30,12 @@ impl Client {
             .with_argument(String::from("factor"), factor)?
             .serialize()?;
 
-        let payload = self
+        let response = self
             .inner
-            .send_request::<i64>(self.base_url.clone(), body)
-            .await?
-            .payload;
+            .send(&self.base_url, body.into())
+            .await
+            .map_err(jsonrpc_client::Error::Client)?;
+        let payload = serde_json::from_slice::<Response<i64>>(&response)?.payload;
         let response = Result::from(payload)?;
 
         Ok(response)
@@ -0,0 +1,31 @@
+use anyhow::Result;
+use jsonrpc_client::http_unix;
+
+#[jsonrpc_client::api]
+pub trait Math {
+    async fn subtract(&self, subtrahend: i64, minuend: i64) -> i64;
+}
+
+#[jsonrpc_client::implement(Math)]
+struct Client {
+    inner: http_unix::Client,
+    base_url: jsonrpc_client::Url,
+}
+
+impl Client {
+    fn new(base_url: String) -> Result<Self> {
+        Ok(Self {
+            inner: http_unix::Client::new(),
+            base_url: base_url.parse()?,
+        })
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let client = Client::new("http+unix://%2Frun%2Fnode.sock/rpc".to_owned())?;
+
+    let _ = client.subtract(10, 5).await?;
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anyhow::{Context, Result};
+use jsonrpc_client::{stream::StreamClient, unix, Url};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct GetInfo {
+    pub id: String,
+    pub alias: String,
+    pub num_peers: u64,
+    pub blockheight: u64,
+    pub network: String,
+}
+
+#[jsonrpc_client::api]
+pub trait LightningRpc {
+    async fn getinfo(&self) -> GetInfo;
+}
+
+#[jsonrpc_client::implement(LightningRpc)]
+struct Client {
+    inner: StreamClient,
+    base_url: Url,
+}
+
+impl Client {
+    async fn connect(socket_path: &str) -> Result<Self> {
+        let base_url = unix::url_from_path(socket_path)
+            .with_context(|| format!("failed to resolve socket path {}", socket_path))?;
+
+        Ok(Self {
+            inner: unix::connect(&base_url).await?,
+            base_url,
+        })
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let socket_path = std::env::args()
+        .nth(1)
+        .context("usage: lightning <path-to-lightning-rpc>")?;
+
+    let client = Client::connect(&socket_path).await?;
+
+    let info = client.getinfo().await?;
+
+    println!("{:?}", info);
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use anyhow::Result;
+use jsonrpc_client::{stream::StreamClient, tcp, Url};
+
+#[jsonrpc_client::api]
+pub trait Math {
+    async fn subtract(&self, subtrahend: i64, minuend: i64) -> i64;
+}
+
+#[jsonrpc_client::implement(Math)]
+struct Client {
+    inner: StreamClient,
+    base_url: Url,
+}
+
+impl Client {
+    async fn connect(base_url: &str) -> Result<Self> {
+        let base_url = base_url.parse()?;
+
+        Ok(Self {
+            inner: tcp::connect(&base_url).await?,
+            base_url,
+        })
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let client = Client::connect("tcp://localhost:4444").await?;
+
+    let _ = client.subtract(10, 5).await?;
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use anyhow::Result;
+use hyper::{body::Bytes, Body};
+use jsonrpc_client::tower::{BoxError, ServiceTransport};
+use std::time::Duration;
+use tower::{util::BoxCloneService, ServiceBuilder};
+
+type HttpService = BoxCloneService<hyper::Request<Bytes>, hyper::Response<Bytes>, BoxError>;
+
+#[jsonrpc_client::api]
+pub trait Math {
+    async fn subtract(&self, subtrahend: i64, minuend: i64) -> i64;
+}
+
+#[jsonrpc_client::implement(Math)]
+struct Client {
+    inner: ServiceTransport<HttpService>,
+    base_url: jsonrpc_client::Url,
+}
+
+impl Client {
+    fn new(base_url: String) -> Result<Self> {
+        let service = ServiceBuilder::new()
+            .timeout(Duration::from_secs(10))
+            .map_request(|request: hyper::Request<Bytes>| request.map(Body::from))
+            .and_then(|response: hyper::Response<Body>| async move {
+                let (parts, body) = response.into_parts();
+                let body = hyper::body::to_bytes(body).await?;
+
+                Ok::<_, hyper::Error>(hyper::Response::from_parts(parts, body))
+            })
+            .service(hyper::Client::new());
+
+        Ok(Self {
+            inner: ServiceTransport::new(BoxCloneService::new(service)),
+            base_url: base_url.parse()?,
+        })
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let client = Client::new("http://example-jsonrpc.org/".to_owned())?;
+
+    let _ = client.subtract(10, 5).await?;
+
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use anyhow::Result;
+
+#[jsonrpc_client::api(blocking)]
+pub trait Math {
+    async fn subtract(&self, subtrahend: i64, minuend: i64) -> i64;
+}
+
+#[jsonrpc_client::implement_blocking(MathBlocking)]
+struct Client {
+    inner: ureq::Agent,
+    base_url: jsonrpc_client::Url,
+}
+
+impl Client {
+    fn new(base_url: String) -> Result<Self> {
+        Ok(Self {
+            inner: ureq::Agent::new(),
+            base_url: base_url.parse()?,
+        })
+    }
+}
+
+fn main() -> Result<()> {
+    let client = Client::new("http://example-jsonrpc.org/".to_owned())?;
+
+    let _ = client.subtract(10, 5)?;
+
+    Ok(())
+}
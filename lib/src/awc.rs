@@ -0,0 +1,120 @@
+use crate::{TransportLocal, Url};
+use awc::{
+    error::{PayloadError, SendRequestError},
+    http::header::CONTENT_TYPE,
+};
+use bytes::Bytes;
+use percent_encoding::percent_decode_str;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum Error {
+    SendRequest(SendRequestError),
+    Payload(PayloadError),
+}
+
+impl From<SendRequestError> for Error {
+    fn from(e: SendRequestError) -> Self {
+        Error::SendRequest(e)
+    }
+}
+
+impl From<PayloadError> for Error {
+    fn from(e: PayloadError) -> Self {
+        Error::Payload(e)
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::SendRequest(inner) => Some(inner),
+            Error::Payload(inner) => Some(inner),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::SendRequest(inner) => fmt::Display::fmt(inner, f),
+            Error::Payload(inner) => fmt::Display::fmt(inner, f),
+        }
+    }
+}
+
+/// The default limit for the size of a response body: 64 MiB.
+///
+/// awc itself limits bodies to 2 MiB, which is too little for many JSON-RPC APIs.
+pub const DEFAULT_MAX_RESPONSE_SIZE: usize = 64 * 1024 * 1024;
+
+/// An [`awc::Client`] that fails with [`PayloadError::Overflow`] on responses larger than a given size.
+///
+/// `awc::Client` itself implements [`TransportLocal`] with a limit of [`DEFAULT_MAX_RESPONSE_SIZE`].
+#[derive(Clone)]
+pub struct Client {
+    inner: awc::Client,
+    max_response_size: usize,
+}
+
+impl Client {
+    /// Wraps the given client, limiting its responses to `max_response_size` bytes.
+    pub fn new(inner: awc::Client, max_response_size: usize) -> Self {
+        Self {
+            inner,
+            max_response_size,
+        }
+    }
+}
+
+/// Credentials contained in the target are sent through the `Authorization` header.
+#[async_trait::async_trait(?Send)]
+impl TransportLocal for awc::Client {
+    type Error = Error;
+
+    async fn send(&self, target: &Url, body: Bytes) -> Result<Bytes, Self::Error> {
+        send(self, target, body, DEFAULT_MAX_RESPONSE_SIZE).await
+    }
+}
+
+/// Credentials contained in the target are sent through the `Authorization` header.
+#[async_trait::async_trait(?Send)]
+impl TransportLocal for Client {
+    type Error = Error;
+
+    async fn send(&self, target: &Url, body: Bytes) -> Result<Bytes, Self::Error> {
+        send(&self.inner, target, body, self.max_response_size).await
+    }
+}
+
+async fn send(
+    client: &awc::Client,
+    target: &Url,
+    body: Bytes,
+    max_response_size: usize,
+) -> Result<Bytes, Error> {
+    let mut url = target.clone();
+    let _ = url.set_username("");
+    let _ = url.set_password(None);
+
+    let mut request = client
+        .post(url.as_str())
+        .insert_header((CONTENT_TYPE, "application/json"));
+    if !target.username().is_empty() || target.password().is_some() {
+        let username = percent_decode_str(target.username()).decode_utf8_lossy();
+        let password =
+            percent_decode_str(target.password().unwrap_or_default()).decode_utf8_lossy();
+
+        request = request.basic_auth(username, password);
+    }
+
+    let mut response = request.send_body(body).await?;
+
+    Ok(response.body().limit(max_response_size).await?)
+}
+
+impl From<Error> for crate::Error<Error> {
+    fn from(inner: Error) -> Self {
+        crate::Error::Client(inner)
+    }
+}
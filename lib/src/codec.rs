@@ -0,0 +1,527 @@
+//! Framing codecs for stream-oriented transports.
+//!
+//! A codec splits the incoming byte stream into frames that each contain exactly one JSON-RPC message and frames outgoing messages before they are written to the stream.
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use std::io;
+use tokio_util::codec::{Decoder, Encoder};
+
+/// The default limit for the length of a single frame: 64 MiB.
+///
+/// Decoders fail with [`io::ErrorKind::InvalidData`] once a frame exceeds their limit, which bounds the memory a misbehaving peer can make them allocate.
+pub const DEFAULT_MAX_FRAME_LENGTH: usize = 64 * 1024 * 1024;
+
+/// A codec that can be used to frame JSON-RPC messages on a stream.
+///
+/// This trait is automatically implemented for all types that implement the necessary [`Decoder`] and [`Encoder`] traits.
+pub trait Codec:
+    Decoder<Item = BytesMut, Error = io::Error>
+    + Encoder<Bytes, Error = io::Error>
+    + Clone
+    + Send
+    + 'static
+{
+}
+
+impl<C> Codec for C where
+    C: Decoder<Item = BytesMut, Error = io::Error>
+        + Encoder<Bytes, Error = io::Error>
+        + Clone
+        + Send
+        + 'static
+{
+}
+
+/// Frames messages by terminating each of them with a newline (`\n`).
+///
+/// This is the framing used by Electrum servers and many embedded devices.
+/// Blank lines and a trailing `\r` are ignored when decoding.
+#[derive(Debug, Clone)]
+pub struct NewlineCodec {
+    next_index: usize,
+    max_frame_length: usize,
+}
+
+impl NewlineCodec {
+    /// Creates a codec that fails on lines longer than `max_frame_length` bytes.
+    pub fn new(max_frame_length: usize) -> Self {
+        Self {
+            next_index: 0,
+            max_frame_length,
+        }
+    }
+}
+
+impl Default for NewlineCodec {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_FRAME_LENGTH)
+    }
+}
+
+impl Decoder for NewlineCodec {
+    type Item = BytesMut;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> io::Result<Option<BytesMut>> {
+        loop {
+            let newline_offset = match src[self.next_index..].iter().position(|b| *b == b'\n') {
+                Some(offset) => offset,
+                None => {
+                    self.next_index = src.len();
+                    check_frame_length(src.len(), self.max_frame_length)?;
+
+                    return Ok(None);
+                }
+            };
+
+            let newline_index = self.next_index + newline_offset;
+            self.next_index = 0;
+            check_frame_length(newline_index, self.max_frame_length)?;
+
+            let mut line = src.split_to(newline_index + 1);
+            line.truncate(newline_index);
+            if line.last() == Some(&b'\r') {
+                line.truncate(newline_index - 1);
+            }
+
+            if !is_blank(&line) {
+                return Ok(Some(line));
+            }
+        }
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> io::Result<Option<BytesMut>> {
+        if let Some(line) = self.decode(src)? {
+            return Ok(Some(line));
+        }
+
+        self.next_index = 0;
+        let rest = src.split();
+
+        if is_blank(&rest) {
+            return Ok(None);
+        }
+
+        Ok(Some(rest))
+    }
+}
+
+impl Encoder<Bytes> for NewlineCodec {
+    type Error = io::Error;
+
+    fn encode(&mut self, item: Bytes, dst: &mut BytesMut) -> io::Result<()> {
+        dst.reserve(item.len() + 1);
+        dst.put(item);
+        dst.put_u8(b'\n');
+
+        Ok(())
+    }
+}
+
+/// Frames messages by prefixing each of them with a `Content-Length` header.
+///
+/// This is the framing defined by the [Language Server Protocol](https://microsoft.github.io/language-server-protocol/specifications/specification-current/#headerPart).
+/// Headers other than `Content-Length` are ignored when decoding.
+#[derive(Debug, Clone)]
+pub struct ContentLengthCodec {
+    content_length: Option<usize>,
+    max_frame_length: usize,
+}
+
+impl ContentLengthCodec {
+    /// Creates a codec that fails on messages whose headers or content are longer than `max_frame_length` bytes.
+    pub fn new(max_frame_length: usize) -> Self {
+        Self {
+            content_length: None,
+            max_frame_length,
+        }
+    }
+}
+
+impl Default for ContentLengthCodec {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_FRAME_LENGTH)
+    }
+}
+
+impl Decoder for ContentLengthCodec {
+    type Item = BytesMut;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> io::Result<Option<BytesMut>> {
+        let content_length = match self.content_length {
+            Some(content_length) => content_length,
+            None => {
+                let header_end = match src.windows(4).position(|window| window == b"\r\n\r\n") {
+                    Some(index) => index,
+                    None => {
+                        check_frame_length(src.len(), self.max_frame_length)?;
+
+                        return Ok(None);
+                    }
+                };
+                check_frame_length(header_end, self.max_frame_length)?;
+
+                let headers = src.split_to(header_end + 4);
+                let content_length = parse_content_length(&headers[..header_end])?;
+                check_frame_length(content_length, self.max_frame_length)?;
+                self.content_length = Some(content_length);
+
+                content_length
+            }
+        };
+
+        if src.len() < content_length {
+            src.reserve(content_length - src.len());
+            return Ok(None);
+        }
+
+        self.content_length = None;
+
+        Ok(Some(src.split_to(content_length)))
+    }
+}
+
+impl Encoder<Bytes> for ContentLengthCodec {
+    type Error = io::Error;
+
+    fn encode(&mut self, item: Bytes, dst: &mut BytesMut) -> io::Result<()> {
+        let header = format!("Content-Length: {}\r\n\r\n", item.len());
+
+        dst.reserve(header.len() + item.len());
+        dst.put(header.as_bytes());
+        dst.put(item);
+
+        Ok(())
+    }
+}
+
+fn parse_content_length(headers: &[u8]) -> io::Result<usize> {
+    let headers =
+        std::str::from_utf8(headers).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    headers
+        .split("\r\n")
+        .find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                Some(value.trim())
+            } else {
+                None
+            }
+        })
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing Content-Length header"))?
+        .parse()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Frames messages by concatenating them without any delimiter.
+///
+/// This is the framing used by the RPC socket of Core Lightning.
+/// The decoder incrementally scans the stream for the end of each JSON object or array, hence whitespace between messages is ignored and newline-delimited messages are decoded as well.
+#[derive(Debug, Clone)]
+pub struct JsonStreamCodec {
+    scanned: usize,
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    max_frame_length: usize,
+}
+
+impl JsonStreamCodec {
+    /// Creates a codec that fails on messages longer than `max_frame_length` bytes.
+    pub fn new(max_frame_length: usize) -> Self {
+        Self {
+            scanned: 0,
+            depth: 0,
+            in_string: false,
+            escaped: false,
+            max_frame_length,
+        }
+    }
+}
+
+impl Default for JsonStreamCodec {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_FRAME_LENGTH)
+    }
+}
+
+impl Decoder for JsonStreamCodec {
+    type Item = BytesMut;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> io::Result<Option<BytesMut>> {
+        if self.scanned == 0 {
+            let start = src
+                .iter()
+                .position(|b| !b.is_ascii_whitespace())
+                .unwrap_or_else(|| src.len());
+            src.advance(start);
+
+            match src.first() {
+                None => return Ok(None),
+                Some(b'{') | Some(b'[') => {}
+                Some(_) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "expected a JSON object or array",
+                    ))
+                }
+            }
+        }
+
+        for index in self.scanned..src.len() {
+            let byte = src[index];
+
+            if self.in_string {
+                if self.escaped {
+                    self.escaped = false;
+                } else if byte == b'\\' {
+                    self.escaped = true;
+                } else if byte == b'"' {
+                    self.in_string = false;
+                }
+
+                continue;
+            }
+
+            match byte {
+                b'"' => self.in_string = true,
+                b'{' | b'[' => self.depth += 1,
+                b'}' | b']' => {
+                    self.depth -= 1;
+
+                    if self.depth == 0 {
+                        self.scanned = 0;
+                        check_frame_length(index + 1, self.max_frame_length)?;
+
+                        return Ok(Some(src.split_to(index + 1)));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        self.scanned = src.len();
+        check_frame_length(src.len(), self.max_frame_length)?;
+
+        Ok(None)
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> io::Result<Option<BytesMut>> {
+        if let Some(message) = self.decode(src)? {
+            return Ok(Some(message));
+        }
+
+        if is_blank(src) {
+            return Ok(None);
+        }
+
+        Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "stream ended in the middle of a JSON value",
+        ))
+    }
+}
+
+impl Encoder<Bytes> for JsonStreamCodec {
+    type Error = io::Error;
+
+    fn encode(&mut self, item: Bytes, dst: &mut BytesMut) -> io::Result<()> {
+        dst.extend_from_slice(&item);
+
+        Ok(())
+    }
+}
+
+fn check_frame_length(length: usize, max_frame_length: usize) -> io::Result<()> {
+    if length > max_frame_length {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "frame exceeds the maximum length of {} bytes",
+                max_frame_length
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+fn is_blank(bytes: &[u8]) -> bool {
+    bytes.iter().all(u8::is_ascii_whitespace)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn newline_codec_decodes_multiple_lines_in_one_buffer() {
+        let mut codec = NewlineCodec::default();
+        let mut buffer = BytesMut::from(&b"{\"id\":1}\n{\"id\":2}\r\n\n{\"id\":"[..]);
+
+        assert_eq!(
+            codec.decode(&mut buffer).unwrap().unwrap(),
+            &b"{\"id\":1}"[..]
+        );
+        assert_eq!(
+            codec.decode(&mut buffer).unwrap().unwrap(),
+            &b"{\"id\":2}"[..]
+        );
+        assert_eq!(codec.decode(&mut buffer).unwrap(), None);
+
+        buffer.extend_from_slice(b"3}\n");
+
+        assert_eq!(
+            codec.decode(&mut buffer).unwrap().unwrap(),
+            &b"{\"id\":3}"[..]
+        );
+    }
+
+    #[test]
+    fn newline_codec_yields_unterminated_line_on_eof() {
+        let mut codec = NewlineCodec::default();
+        let mut buffer = BytesMut::from(&b"{\"id\":1}"[..]);
+
+        assert_eq!(codec.decode(&mut buffer).unwrap(), None);
+        assert_eq!(
+            codec.decode_eof(&mut buffer).unwrap().unwrap(),
+            &b"{\"id\":1}"[..]
+        );
+        assert_eq!(codec.decode_eof(&mut buffer).unwrap(), None);
+    }
+
+    #[test]
+    fn newline_codec_rejects_line_above_limit() {
+        let mut codec = NewlineCodec::new(8);
+        let mut buffer = BytesMut::from(&b"{\"id\":12345,"[..]);
+
+        let error = codec.decode(&mut buffer).unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn newline_codec_terminates_encoded_message() {
+        let mut codec = NewlineCodec::default();
+        let mut buffer = BytesMut::new();
+
+        codec
+            .encode(Bytes::from_static(b"{\"id\":1}"), &mut buffer)
+            .unwrap();
+
+        assert_eq!(buffer, &b"{\"id\":1}\n"[..]);
+    }
+
+    #[test]
+    fn content_length_codec_decodes_message_split_across_reads() {
+        let mut codec = ContentLengthCodec::default();
+        let mut buffer = BytesMut::from(&b"Content-Length: 8\r\nContent-Type: application/vscode-jsonrpc; charset=utf-8\r\n\r\n{\"id\""[..]);
+
+        assert_eq!(codec.decode(&mut buffer).unwrap(), None);
+
+        buffer.extend_from_slice(b":1}Content-Length: 8\r\n\r\n{\"id\":2}");
+
+        assert_eq!(
+            codec.decode(&mut buffer).unwrap().unwrap(),
+            &b"{\"id\":1}"[..]
+        );
+        assert_eq!(
+            codec.decode(&mut buffer).unwrap().unwrap(),
+            &b"{\"id\":2}"[..]
+        );
+        assert_eq!(codec.decode(&mut buffer).unwrap(), None);
+    }
+
+    #[test]
+    fn content_length_codec_rejects_message_without_content_length() {
+        let mut codec = ContentLengthCodec::default();
+        let mut buffer = BytesMut::from(&b"Content-Type: application/json\r\n\r\n{}"[..]);
+
+        let error = codec.decode(&mut buffer).unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn content_length_codec_rejects_content_length_above_limit() {
+        let mut codec = ContentLengthCodec::new(1024);
+        let mut buffer = BytesMut::from(&b"Content-Length: 18446744073709551000\r\n\r\n{}"[..]);
+
+        let error = codec.decode(&mut buffer).unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(buffer.capacity() < 1024);
+    }
+
+    #[test]
+    fn content_length_codec_prefixes_encoded_message() {
+        let mut codec = ContentLengthCodec::default();
+        let mut buffer = BytesMut::new();
+
+        codec
+            .encode(Bytes::from_static(b"{\"id\":1}"), &mut buffer)
+            .unwrap();
+
+        assert_eq!(buffer, &b"Content-Length: 8\r\n\r\n{\"id\":1}"[..]);
+    }
+
+    #[test]
+    fn json_stream_codec_decodes_concatenated_values_split_across_reads() {
+        let mut codec = JsonStreamCodec::default();
+        let mut buffer = BytesMut::from(&b"{\"id\":1,\"result\":{\"alias\":\"}{[\\\""[..]);
+
+        assert_eq!(codec.decode(&mut buffer).unwrap(), None);
+
+        buffer.extend_from_slice(b"\"}}\n\n[1,[2]]{\"id\":2}");
+
+        assert_eq!(
+            codec.decode(&mut buffer).unwrap().unwrap(),
+            &b"{\"id\":1,\"result\":{\"alias\":\"}{[\\\"\"}}"[..]
+        );
+        assert_eq!(codec.decode(&mut buffer).unwrap().unwrap(), &b"[1,[2]]"[..]);
+        assert_eq!(
+            codec.decode(&mut buffer).unwrap().unwrap(),
+            &b"{\"id\":2}"[..]
+        );
+        assert_eq!(codec.decode(&mut buffer).unwrap(), None);
+    }
+
+    #[test]
+    fn json_stream_codec_rejects_scalar_values() {
+        let mut codec = JsonStreamCodec::default();
+        let mut buffer = BytesMut::from(&b" 42"[..]);
+
+        let error = codec.decode(&mut buffer).unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn json_stream_codec_rejects_value_above_limit() {
+        let mut codec = JsonStreamCodec::new(8);
+        let mut buffer = BytesMut::from(&b"{\"id\":12345,"[..]);
+
+        let error = codec.decode(&mut buffer).unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn json_stream_codec_fails_on_truncated_value_at_eof() {
+        let mut codec = JsonStreamCodec::default();
+        let mut buffer = BytesMut::from(&b"{\"id\":1}\n{\"id\""[..]);
+
+        assert_eq!(
+            codec.decode_eof(&mut buffer).unwrap().unwrap(),
+            &b"{\"id\":1}"[..]
+        );
+
+        let error = codec.decode_eof(&mut buffer).unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
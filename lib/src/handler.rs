@@ -0,0 +1,150 @@
+//! A registry of handlers for requests that are initiated by the server.
+//!
+//! On bidirectional transports, the server may call methods on the client, for example `workspace/configuration` in LSP.
+
+use crate::{Id, JsonRpcError, Version};
+use futures_util::future::BoxFuture;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{
+    collections::HashMap,
+    fmt,
+    future::Future,
+    sync::{Arc, RwLock},
+};
+
+type BoxedHandler =
+    Arc<dyn Fn(Value) -> BoxFuture<'static, Result<Value, JsonRpcError>> + Send + Sync>;
+
+/// A request sent by the server.
+#[derive(Deserialize, Debug)]
+struct IncomingRequest {
+    id: Id,
+    #[serde(default)]
+    jsonrpc: Option<Version>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Default)]
+pub(crate) struct Handlers(RwLock<HashMap<String, BoxedHandler>>);
+
+impl fmt::Debug for Handlers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set()
+            .entries(self.0.read().unwrap().keys())
+            .finish()
+    }
+}
+
+impl Handlers {
+    pub(crate) fn insert<F, Fut, P, R>(&self, method: &str, handler: F)
+    where
+        F: Fn(P) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<R, JsonRpcError>> + Send + 'static,
+        P: DeserializeOwned,
+        R: Serialize,
+    {
+        let handler: BoxedHandler = Arc::new(move |params| {
+            let params = match serde_json::from_value(params) {
+                Ok(params) => params,
+                Err(e) => {
+                    return Box::pin(futures_util::future::ready(Err(JsonRpcError {
+                        code: -32602,
+                        message: format!("invalid params: {}", e),
+                        data: None,
+                    })))
+                }
+            };
+            let response = handler(params);
+
+            Box::pin(async move {
+                let result = response.await?;
+
+                serde_json::to_value(result).map_err(|e| JsonRpcError {
+                    code: -32603,
+                    message: format!("failed to serialize result: {}", e),
+                    data: None,
+                })
+            })
+        });
+
+        self.0.write().unwrap().insert(method.to_owned(), handler);
+    }
+
+    /// Dispatches the request to the registered handler and returns the response that should be sent back to the server.
+    ///
+    /// Returns `None` if the message is not a valid request.
+    pub(crate) async fn dispatch(&self, request: Value) -> Option<Value> {
+        let request = serde_json::from_value::<IncomingRequest>(request).ok()?;
+
+        // release the lock before calling the handler, which may register handlers itself
+        let handler = self.0.read().unwrap().get(&request.method).cloned();
+
+        let response = match handler {
+            Some(handler) => handler(request.params),
+            None => {
+                let error = JsonRpcError {
+                    code: -32601,
+                    message: format!("method `{}` not found", request.method),
+                    data: None,
+                };
+
+                Box::pin(futures_util::future::ready(Err(error)))
+            }
+        };
+        let response = response.await;
+
+        let response = match (request.jsonrpc, response) {
+            (Some(Version::V2), Ok(result)) => {
+                json!({ "jsonrpc": "2.0", "id": request.id, "result": result })
+            }
+            (Some(Version::V2), Err(error)) => {
+                json!({ "jsonrpc": "2.0", "id": request.id, "error": error })
+            }
+            (_, Ok(result)) => json!({ "id": request.id, "result": result, "error": null }),
+            (_, Err(error)) => json!({ "id": request.id, "result": null, "error": error }),
+        };
+
+        Some(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn handler_may_register_handlers() {
+        let handlers = Arc::new(Handlers::default());
+        handlers.insert("initialize", {
+            let handlers = Arc::downgrade(&handlers);
+
+            move |_: Value| {
+                handlers
+                    .upgrade()
+                    .unwrap()
+                    .insert("shutdown", |_: Value| async { Ok::<_, JsonRpcError>(()) });
+
+                async { Ok::<_, JsonRpcError>(()) }
+            }
+        });
+
+        let initialize = handlers
+            .dispatch(json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize" }))
+            .await;
+        let shutdown = handlers
+            .dispatch(json!({ "jsonrpc": "2.0", "id": 2, "method": "shutdown" }))
+            .await;
+
+        assert_eq!(
+            initialize,
+            Some(json!({ "jsonrpc": "2.0", "id": 1, "result": null }))
+        );
+        assert_eq!(
+            shutdown,
+            Some(json!({ "jsonrpc": "2.0", "id": 2, "result": null }))
+        );
+    }
+}
@@ -0,0 +1,204 @@
+//! JSON-RPC over HTTP/1.1 on Unix domain sockets.
+//!
+//! This is how docker-style daemons and many local wallets expose their API.
+//! Endpoints are addressed through URLs of the form `http+unix://%2Frun%2Fnode.sock/rpc`, where the host is the percent-encoded path of the socket.
+//!
+//! Requests are sent with [`hyper`] and use the same error type as the [`hyper`](crate::hyper) backend.
+
+use crate::{hyper::Error, ByteStream, Transport, Url};
+use futures_util::TryStreamExt;
+use hyper::{
+    body::Bytes,
+    client::connect::{Connected, Connection},
+    service::Service,
+    Body, Uri,
+};
+use percent_encoding::percent_decode_str;
+use std::{
+    ffi::OsStr,
+    future::Future,
+    io,
+    os::unix::ffi::OsStrExt,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::{
+    io::{AsyncRead, AsyncWrite, ReadBuf},
+    net::UnixStream,
+};
+
+/// The scheme of URLs that address an HTTP server on a Unix domain socket.
+pub const SCHEME: &str = "http+unix";
+
+/// A client that sends requests to `http+unix://` URLs.
+///
+/// Connections to the same socket are pooled.
+#[derive(Debug, Clone)]
+pub struct Client {
+    inner: hyper::Client<UnixConnector, Body>,
+}
+
+impl Client {
+    /// Creates a client with a fresh connection pool.
+    pub fn new() -> Self {
+        Self {
+            inner: hyper::Client::builder().build(UnixConnector),
+        }
+    }
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Client {
+    /// Translates the percent-encoded socket path of the target into the form the [`UnixConnector`] expects.
+    async fn request(&self, target: &Url, body: Bytes) -> Result<hyper::Response<Body>, Error> {
+        let mut target = target.clone();
+
+        // URIs must not contain percent-encoded hosts, hence we hand the path to the connector hex-encoded
+        if target.scheme() == SCHEME {
+            if let Some(host) = target.host_str() {
+                let path = percent_decode_str(host).collect::<Vec<_>>();
+                let _ = target.set_host(Some(&hex_encode(&path)));
+            }
+        }
+
+        crate::hyper::send(&self.inner, &target, body).await
+    }
+}
+
+#[async_trait::async_trait]
+impl Transport for Client {
+    type Error = Error;
+
+    async fn send(&self, target: &Url, body: Bytes) -> Result<Bytes, Self::Error> {
+        let response = self.request(target, body).await?;
+
+        Ok(hyper::body::to_bytes(response.into_body()).await?)
+    }
+
+    async fn send_streaming(
+        &self,
+        target: &Url,
+        body: Bytes,
+    ) -> Result<ByteStream<Self::Error>, Self::Error>
+    where
+        Self: Sync,
+        Self::Error: Send + 'static,
+    {
+        let response = self.request(target, body).await?;
+
+        Ok(Box::pin(response.into_body().map_err(Error::Hyper)))
+    }
+}
+
+/// A connector for [`hyper::Client`] that connects to the socket whose hex-encoded path is the host of a `http+unix://` URI.
+///
+/// [`Client`] takes care of translating the percent-encoded path of the URL into this form.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UnixConnector;
+
+impl Service<Uri> for UnixConnector {
+    type Response = UnixConnection;
+    type Error = io::Error;
+    #[allow(clippy::type_complexity)]
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        Box::pin(async move {
+            let path = match (uri.scheme_str(), uri.host().and_then(hex_decode)) {
+                (Some(SCHEME), Some(path)) => path,
+                _ => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!(
+                            "expected a URL of the form `{}://%2Fpath%2Fto%2Fsocket`, got `{}`",
+                            SCHEME, uri
+                        ),
+                    ))
+                }
+            };
+
+            let stream = UnixStream::connect(OsStr::from_bytes(&path)).await?;
+
+            Ok(UnixConnection(stream))
+        })
+    }
+}
+
+/// A connection established by the [`UnixConnector`].
+#[derive(Debug)]
+pub struct UnixConnection(UnixStream);
+
+impl Connection for UnixConnection {
+    fn connected(&self) -> Connected {
+        Connected::new()
+    }
+}
+
+impl AsyncRead for UnixConnection {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for UnixConnection {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.0).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_shutdown(cx)
+    }
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn hex_decode(hex: &str) -> Option<Vec<u8>> {
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| match pair {
+            [high, low] => Some((hex_digit(*high)? << 4) | hex_digit(*low)?),
+            _ => None,
+        })
+        .collect()
+}
+
+fn hex_digit(digit: u8) -> Option<u8> {
+    (digit as char).to_digit(16).map(|digit| digit as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_roundtrip() {
+        let path = b"/run/node rpc.sock";
+
+        assert_eq!(hex_decode(&hex_encode(path)).unwrap(), path);
+        assert_eq!(hex_decode("2f7"), None);
+        assert_eq!(hex_decode("zz"), None);
+    }
+}
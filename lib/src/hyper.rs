@@ -0,0 +1,131 @@
+use crate::{ByteStream, Transport, Url};
+use base64::Engine;
+use futures_util::TryStreamExt;
+use hyper::body::Bytes;
+use hyper::{
+    body::Body,
+    client::connect::Connect,
+    header::{HeaderValue, AUTHORIZATION, CONTENT_TYPE},
+    Request,
+};
+use percent_encoding::percent_decode_str;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum Error {
+    Hyper(hyper::Error),
+    Http(hyper::http::Error),
+}
+
+impl From<hyper::Error> for Error {
+    fn from(e: hyper::Error) -> Self {
+        Error::Hyper(e)
+    }
+}
+
+impl From<hyper::http::Error> for Error {
+    fn from(e: hyper::http::Error) -> Self {
+        Error::Http(e)
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Hyper(inner) => Some(inner),
+            Error::Http(inner) => Some(inner),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Hyper(inner) => fmt::Display::fmt(inner, f),
+            Error::Http(inner) => fmt::Display::fmt(inner, f),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<C> Transport for hyper::Client<C, Body>
+where
+    C: Connect + Clone + Send + Sync + 'static,
+{
+    type Error = Error;
+
+    async fn send(&self, target: &Url, body: Bytes) -> Result<Bytes, Self::Error> {
+        let response = send(self, target, body).await?;
+
+        Ok(hyper::body::to_bytes(response.into_body()).await?)
+    }
+
+    async fn send_streaming(
+        &self,
+        target: &Url,
+        body: Bytes,
+    ) -> Result<ByteStream<Self::Error>, Self::Error>
+    where
+        Self: Sync,
+        Self::Error: Send + 'static,
+    {
+        let response = send(self, target, body).await?;
+
+        Ok(Box::pin(response.into_body().map_err(Error::Hyper)))
+    }
+}
+
+/// Sends the request with the given client and returns the response once its head arrived.
+///
+/// Credentials contained in the target are sent through the `Authorization` header.
+pub(crate) async fn send<C>(
+    client: &hyper::Client<C, Body>,
+    target: &Url,
+    body: Bytes,
+) -> Result<hyper::Response<Body>, Error>
+where
+    C: Connect + Clone + Send + Sync + 'static,
+{
+    let authorization = basic_auth(target);
+    let mut target = target.clone();
+    let _ = target.set_username("");
+    let _ = target.set_password(None);
+
+    let mut request = Request::post(target.as_str())
+        .header(CONTENT_TYPE, "application/json")
+        .body(Body::from(body))?;
+    if let Some(authorization) = authorization {
+        request.headers_mut().insert(AUTHORIZATION, authorization);
+    }
+
+    Ok(client.request(request).await?)
+}
+
+fn basic_auth(endpoint: &Url) -> Option<HeaderValue> {
+    if endpoint.username().is_empty() && endpoint.password().is_none() {
+        return None;
+    }
+
+    let username = percent_decode_str(endpoint.username()).collect::<Vec<_>>();
+    let password = percent_decode_str(endpoint.password().unwrap_or_default()).collect::<Vec<_>>();
+
+    let mut credentials = username;
+    credentials.push(b':');
+    credentials.extend(password);
+
+    let value = format!(
+        "Basic {}",
+        base64::engine::general_purpose::STANDARD.encode(credentials)
+    );
+
+    let mut value = HeaderValue::from_str(&value).expect("base64 is a valid header value");
+    value.set_sensitive(true);
+
+    Some(value)
+}
+
+impl From<Error> for crate::Error<Error> {
+    fn from(inner: Error) -> Self {
+        crate::Error::Client(inner)
+    }
+}
@@ -1,33 +1,32 @@
-use crate::{Response, SendRequest, Url};
+use crate::{Transport, Url};
+use bytes::Bytes;
+use futures_util::{io::Cursor, AsyncReadExt};
 use isahc::{
     http::{
         header::{HeaderValue, CONTENT_TYPE},
         Request,
     },
-    ResponseExt,
+    Body,
 };
-use serde::de::DeserializeOwned;
 
 #[async_trait::async_trait]
-impl SendRequest for isahc::HttpClient {
+impl Transport for isahc::HttpClient {
     type Error = isahc::Error;
 
-    async fn send_request<P>(&self, endpoint: Url, body: String) -> Result<Response<P>, Self::Error>
-    where
-        P: DeserializeOwned,
-    {
-        let mut request = Request::post(endpoint.to_string()).body(body)?;
+    async fn send(&self, target: &Url, body: Bytes) -> Result<Bytes, Self::Error> {
+        // isahc only reuses buffers of its own version of `bytes`, reading from this one avoids copying it
+        let length = body.len() as u64;
+        let mut request = Request::post(target.to_string())
+            .body(Body::from_reader_sized(Cursor::new(body), length))?;
         request
             .headers_mut()
             .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
 
-        let response = self
-            .send_async(request)
-            .await?
-            .json()
-            .map_err(|e| isahc::Error::ResponseBodyError(Some(e.to_string())))?;
+        let mut response = self.send_async(request).await?;
+        let mut body = Vec::new();
+        response.body_mut().read_to_end(&mut body).await?;
 
-        Ok(response)
+        Ok(Bytes::from(body))
     }
 }
 
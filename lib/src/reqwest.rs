@@ -1,23 +1,38 @@
-use crate::{Response, SendRequest, Url};
+use crate::{ByteStream, Transport, Url};
+use bytes::Bytes;
 use reqwest::header::CONTENT_TYPE;
-use serde::de::DeserializeOwned;
 
 #[async_trait::async_trait]
-impl SendRequest for reqwest::Client {
+impl Transport for reqwest::Client {
     type Error = reqwest::Error;
 
-    async fn send_request<P>(&self, endpoint: Url, body: String) -> Result<Response<P>, Self::Error>
+    async fn send(&self, target: &Url, body: Bytes) -> Result<Bytes, Self::Error> {
+        self.post(target.clone())
+            .header(CONTENT_TYPE, "application/json")
+            .body(body)
+            .send()
+            .await?
+            .bytes()
+            .await
+    }
+
+    async fn send_streaming(
+        &self,
+        target: &Url,
+        body: Bytes,
+    ) -> Result<ByteStream<Self::Error>, Self::Error>
     where
-        P: DeserializeOwned,
+        Self: Sync,
+        Self::Error: Send + 'static,
     {
-        Ok(self
-            .post(endpoint)
+        let response = self
+            .post(target.clone())
             .header(CONTENT_TYPE, "application/json")
             .body(body)
             .send()
-            .await?
-            .json()
-            .await?)
+            .await?;
+
+        Ok(Box::pin(response.bytes_stream()))
     }
 }
 
@@ -0,0 +1,27 @@
+use crate::{Transport, Url};
+use bytes::Bytes;
+use reqwest::header::CONTENT_TYPE;
+use reqwest_middleware::ClientWithMiddleware;
+
+/// Errors of the middleware and of the underlying `reqwest::Client` are both preserved through [`reqwest_middleware::Error`].
+#[async_trait::async_trait]
+impl Transport for ClientWithMiddleware {
+    type Error = reqwest_middleware::Error;
+
+    async fn send(&self, target: &Url, body: Bytes) -> Result<Bytes, Self::Error> {
+        Ok(self
+            .post(target.clone())
+            .header(CONTENT_TYPE, "application/json")
+            .body(body)
+            .send()
+            .await?
+            .bytes()
+            .await?)
+    }
+}
+
+impl From<reqwest_middleware::Error> for crate::Error<reqwest_middleware::Error> {
+    fn from(inner: reqwest_middleware::Error) -> Self {
+        crate::Error::Client(inner)
+    }
+}
@@ -0,0 +1,115 @@
+//! JSON-RPC over the standard input and output of a child process.
+//!
+//! Messages are framed with LSP-style `Content-Length` headers, see [`ContentLengthCodec`].
+//! This is how language servers and many other developer tools talk JSON-RPC.
+
+use crate::{
+    codec::ContentLengthCodec,
+    stream::{Error, StreamClient},
+    JsonRpcError, Notification, Transport, Url,
+};
+use bytes::Bytes;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{future::Future, io, process::ExitStatus, time::Duration};
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    process::{Child, Command},
+    sync::broadcast,
+};
+
+/// Creates a client that receives messages from `reader` and sends messages to `writer`, framing them with `Content-Length` headers.
+///
+/// # Panics
+///
+/// This function panics if called outside of a Tokio runtime.
+pub fn connect<R, W>(reader: R, writer: W) -> StreamClient
+where
+    R: AsyncRead + Send + 'static,
+    W: AsyncWrite + Send + 'static,
+{
+    StreamClient::from_parts(reader, writer, ContentLengthCodec::default())
+}
+
+/// A child process that is talked to via its standard input and output.
+///
+/// The child is killed if this value is dropped without calling [`ChildProcess::shutdown`] first.
+#[derive(Debug)]
+pub struct ChildProcess {
+    client: StreamClient,
+    child: Child,
+}
+
+impl ChildProcess {
+    /// Spawns the given command with piped standard input and output.
+    ///
+    /// The standard error of the command is left untouched.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if called outside of a Tokio runtime.
+    pub fn spawn(command: &mut Command) -> io::Result<Self> {
+        let mut child = command
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()?;
+
+        let stdin = child.stdin.take().expect("stdin to be piped");
+        let stdout = child.stdout.take().expect("stdout to be piped");
+
+        Ok(Self {
+            client: connect(stdout, stdin),
+            child,
+        })
+    }
+
+    /// Subscribes to the notifications sent by the child process.
+    ///
+    /// See [`StreamClient::notifications`].
+    pub fn notifications(&self) -> broadcast::Receiver<Notification> {
+        self.client.notifications()
+    }
+
+    /// Registers a handler for requests that are sent by the child process.
+    ///
+    /// See [`StreamClient::handle`].
+    pub fn handle<F, Fut, P, R>(&self, method: &str, handler: F)
+    where
+        F: Fn(P) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<R, JsonRpcError>> + Send + 'static,
+        P: DeserializeOwned,
+        R: Serialize,
+    {
+        self.client.handle(method, handler)
+    }
+
+    /// Shuts the child process down.
+    ///
+    /// This closes the standard input of the child and waits for it to exit.
+    /// If the child doesn't exit within the given grace period, it is killed.
+    ///
+    /// Protocols like LSP expect a dedicated request before the connection is closed; send it before calling this function.
+    pub async fn shutdown(self, grace_period: Duration) -> io::Result<ExitStatus> {
+        let Self { client, mut child } = self;
+
+        // dropping the client closes the standard input of the child
+        drop(client);
+
+        match tokio::time::timeout(grace_period, child.wait()).await {
+            Ok(status) => status,
+            Err(_) => {
+                child.kill().await?;
+                child.wait().await
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Transport for ChildProcess {
+    type Error = Error;
+
+    async fn send(&self, target: &Url, body: Bytes) -> Result<Bytes, Self::Error> {
+        self.client.send(target, body).await
+    }
+}
@@ -0,0 +1,365 @@
+//! A multiplexing JSON-RPC client for stream-oriented transports like TCP sockets.
+//!
+//! Contrary to HTTP, a single stream carries many requests and responses.
+//! [`StreamClient`] takes care of assigning unique IDs to outgoing requests, matches incoming responses to the request they belong to and forwards notifications pushed by the server to its subscribers.
+//! Requests initiated by the server are dispatched to the handlers registered through [`StreamClient::handle`].
+
+use crate::{codec::Codec, handler::Handlers, JsonRpcError, Notification, Transport, Url};
+use bytes::Bytes;
+use futures_util::{SinkExt, StreamExt};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+use std::{
+    collections::HashMap,
+    fmt,
+    future::Future,
+    io,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    sync::{broadcast, mpsc, oneshot},
+    task::JoinHandle,
+};
+use tokio_util::codec::{FramedRead, FramedWrite};
+
+/// The number of notifications that are buffered for each subscriber before the oldest ones are dropped.
+const NOTIFICATION_BUFFER: usize = 1024;
+
+/// A JSON-RPC client that multiplexes concurrent requests over a single stream.
+///
+/// The connection is established before the client is constructed.
+/// Hence, the target passed to [`Transport::send`] is ignored.
+///
+/// Reading from and writing to the stream happens on background tasks that are spawned onto the current Tokio runtime.
+/// Dropping the client stops these tasks and closes the stream.
+#[derive(Debug)]
+pub struct StreamClient {
+    next_id: AtomicU64,
+    pending: Arc<Pending>,
+    notifications: broadcast::Sender<Notification>,
+    handlers: Arc<Handlers>,
+    outgoing: mpsc::UnboundedSender<Bytes>,
+    reader: JoinHandle<()>,
+}
+
+impl StreamClient {
+    /// Creates a new client that sends and receives messages on the given stream.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if called outside of a Tokio runtime.
+    pub fn new<T, C>(stream: T, codec: C) -> Self
+    where
+        T: AsyncRead + AsyncWrite + Send + 'static,
+        C: Codec,
+    {
+        let (reader, writer) = tokio::io::split(stream);
+
+        Self::from_parts(reader, writer, codec)
+    }
+
+    /// Creates a new client that receives messages from `reader` and sends messages to `writer`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if called outside of a Tokio runtime.
+    pub fn from_parts<R, W, C>(reader: R, writer: W, codec: C) -> Self
+    where
+        R: AsyncRead + Send + 'static,
+        W: AsyncWrite + Send + 'static,
+        C: Codec,
+    {
+        let pending = Arc::new(Pending::default());
+        let (notifications, _) = broadcast::channel(NOTIFICATION_BUFFER);
+        let handlers = Arc::new(Handlers::default());
+        let (outgoing, outgoing_receiver) = mpsc::unbounded_channel();
+
+        tokio::spawn(write_loop(
+            FramedWrite::new(writer, codec.clone()),
+            outgoing_receiver,
+            pending.clone(),
+        ));
+        let reader = tokio::spawn(read_loop(
+            FramedRead::new(reader, codec),
+            pending.clone(),
+            notifications.clone(),
+            handlers.clone(),
+            outgoing.clone(),
+        ));
+
+        Self {
+            next_id: AtomicU64::new(0),
+            pending,
+            notifications,
+            handlers,
+            outgoing,
+            reader,
+        }
+    }
+
+    /// Subscribes to the notifications pushed by the server.
+    ///
+    /// Only notifications that arrive after the subscription has been created are delivered.
+    pub fn notifications(&self) -> broadcast::Receiver<Notification> {
+        self.notifications.subscribe()
+    }
+
+    /// Registers a handler for requests with the given method that are sent by the server.
+    ///
+    /// The params of the request are deserialized into `P` and the result of the handler is sent back to the server on the same connection.
+    /// Requests for methods without a handler are answered with a "method not found" error.
+    /// Registering another handler for the same method replaces the previous one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use jsonrpc_client::{stream::StreamClient, JsonRpcError};
+    /// # use serde::Deserialize;
+    /// #[derive(Deserialize)]
+    /// struct ShowMessageRequestParams {
+    ///     message: String,
+    /// }
+    ///
+    /// fn register_handlers(client: &StreamClient) {
+    ///     client.handle(
+    ///         "window/showMessageRequest",
+    ///         |params: ShowMessageRequestParams| async move {
+    ///             println!("{}", params.message);
+    ///
+    ///             Ok::<_, JsonRpcError>(())
+    ///         },
+    ///     );
+    /// }
+    /// ```
+    pub fn handle<F, Fut, P, R>(&self, method: &str, handler: F)
+    where
+        F: Fn(P) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<R, JsonRpcError>> + Send + 'static,
+        P: DeserializeOwned,
+        R: Serialize,
+    {
+        self.handlers.insert(method, handler);
+    }
+}
+
+impl Drop for StreamClient {
+    fn drop(&mut self) {
+        self.reader.abort();
+    }
+}
+
+#[async_trait::async_trait]
+impl Transport for StreamClient {
+    type Error = Error;
+
+    async fn send(&self, _: &Url, body: Bytes) -> Result<Bytes, Self::Error> {
+        let mut request = serde_json::from_slice::<Map<String, Value>>(&body)?;
+
+        // requests are built with a static ID, replace it to be able to match the response
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let original_id = request.insert("id".to_owned(), Value::from(id));
+
+        let response = self.pending.insert(id).ok_or(Error::ConnectionClosed)?;
+        let guard = RemoveOnDrop {
+            pending: &self.pending,
+            id,
+        };
+
+        self.outgoing
+            .send(Bytes::from(serde_json::to_vec(&request)?))
+            .map_err(|_| Error::ConnectionClosed)?;
+
+        let mut response = response.await.map_err(|_| Error::ConnectionClosed)?;
+        drop(guard);
+
+        if let Value::Object(response) = &mut response {
+            response.insert("id".to_owned(), original_id.unwrap_or(Value::Null));
+        }
+
+        Ok(Bytes::from(serde_json::to_vec(&response)?))
+    }
+}
+
+/// The requests that are still waiting for a response.
+///
+/// Once the connection is closed, no more requests can be added.
+#[derive(Debug, Default)]
+struct Pending(Mutex<PendingState>);
+
+#[derive(Debug, Default)]
+struct PendingState {
+    requests: HashMap<u64, oneshot::Sender<Value>>,
+    closed: bool,
+}
+
+impl Pending {
+    fn insert(&self, id: u64) -> Option<oneshot::Receiver<Value>> {
+        let mut state = self.0.lock().unwrap();
+
+        if state.closed {
+            return None;
+        }
+
+        let (sender, receiver) = oneshot::channel();
+        state.requests.insert(id, sender);
+
+        Some(receiver)
+    }
+
+    fn resolve(&self, id: u64, response: Value) {
+        let sender = self.0.lock().unwrap().requests.remove(&id);
+
+        if let Some(sender) = sender {
+            let _ = sender.send(response);
+        }
+    }
+
+    fn remove(&self, id: u64) {
+        self.0.lock().unwrap().requests.remove(&id);
+    }
+
+    /// Fails all pending requests and rejects new ones.
+    fn close(&self) {
+        let mut state = self.0.lock().unwrap();
+
+        state.closed = true;
+        state.requests.clear();
+    }
+}
+
+struct RemoveOnDrop<'a> {
+    pending: &'a Pending,
+    id: u64,
+}
+
+impl Drop for RemoveOnDrop<'_> {
+    fn drop(&mut self) {
+        self.pending.remove(self.id);
+    }
+}
+
+async fn read_loop<R, C>(
+    frames: FramedRead<R, C>,
+    pending: Arc<Pending>,
+    notifications: broadcast::Sender<Notification>,
+    handlers: Arc<Handlers>,
+    outgoing: mpsc::UnboundedSender<Bytes>,
+) where
+    R: AsyncRead,
+    C: Codec,
+{
+    futures_util::pin_mut!(frames);
+
+    while let Some(Ok(frame)) = frames.next().await {
+        let message = match serde_json::from_slice::<Map<String, Value>>(&frame) {
+            Ok(message) => message,
+            Err(_) => continue,
+        };
+
+        match (message.get("id"), message.contains_key("method")) {
+            (None, true) | (Some(Value::Null), true) => {
+                if let Ok(notification) = serde_json::from_value(Value::Object(message)) {
+                    let _ = notifications.send(notification);
+                }
+            }
+            (Some(_), true) => {
+                let handlers = handlers.clone();
+                let outgoing = outgoing.clone();
+
+                // handlers may take a while, don't block reading responses in the meantime
+                tokio::spawn(async move {
+                    if let Some(response) = handlers.dispatch(Value::Object(message)).await {
+                        let response = serde_json::to_vec(&response)
+                            .expect("serializing a JSON value never fails");
+                        let _ = outgoing.send(Bytes::from(response));
+                    }
+                });
+            }
+            (Some(id), false) => {
+                if let Some(id) = id.as_u64() {
+                    pending.resolve(id, Value::Object(message));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pending.close();
+}
+
+async fn write_loop<W, C>(
+    sink: FramedWrite<W, C>,
+    mut outgoing: mpsc::UnboundedReceiver<Bytes>,
+    pending: Arc<Pending>,
+) where
+    W: AsyncWrite,
+    C: Codec,
+{
+    futures_util::pin_mut!(sink);
+
+    while let Some(frame) = outgoing.recv().await {
+        if sink.send(frame).await.is_err() {
+            break;
+        }
+    }
+
+    outgoing.close();
+    pending.close();
+    let _ = sink.close().await;
+}
+
+/// The error type of the [`StreamClient`].
+#[derive(Debug)]
+pub enum Error {
+    /// Reading from or writing to the stream failed.
+    Io(io::Error),
+    /// The request or response was not valid JSON.
+    Serde(serde_json::Error),
+    /// The connection was closed before a response was received.
+    ConnectionClosed,
+    /// The URL does not describe an endpoint that can be connected to.
+    UnsupportedUrl(Url),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(inner) => fmt::Display::fmt(inner, f),
+            Error::Serde(inner) => fmt::Display::fmt(inner, f),
+            Error::ConnectionClosed => write!(f, "connection closed"),
+            Error::UnsupportedUrl(url) => write!(f, "unsupported URL: {}", url),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(inner) => Some(inner),
+            Error::Serde(inner) => Some(inner),
+            Error::ConnectionClosed | Error::UnsupportedUrl(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error::Serde(e)
+    }
+}
+
+impl From<Error> for crate::Error<Error> {
+    fn from(inner: Error) -> Self {
+        crate::Error::Client(inner)
+    }
+}
@@ -0,0 +1,220 @@
+//! Incremental deserialization of large responses.
+//!
+//! The functions in this module parse a response while its body is still being received instead of buffering it entirely first.
+//! Only the deserialized result is kept in memory, the body itself is read in small chunks.
+//! To bound the memory used by a misbehaving server, reading stops with [`Error::ResponseTooLarge`](crate::Error::ResponseTooLarge) once the body exceeds a configurable size.
+//!
+//! Parsing happens on a blocking thread of the tokio runtime, which is why a runtime needs to be running.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! # #[cfg(feature = "hyper")]
+//! # async fn getblock() -> Result<(), jsonrpc_client::Error<jsonrpc_client::hyper::Error>> {
+//! use jsonrpc_client::{streaming, RequestWriter};
+//!
+//! let client = hyper::Client::new();
+//! let request = RequestWriter::new_v1("getblock")
+//!     .with_argument("blockhash", "00000000000000000001e4ff7ed1b41fe9ec6b01ec3a3bb06c9eb8f1f4b6a5b3")?
+//!     .with_argument("verbosity", &2)?
+//!     .finish();
+//!
+//! let block = streaming::send::<_, serde_json::Value>(
+//!     &client,
+//!     &"http://localhost:8332".parse().unwrap(),
+//!     request,
+//!     streaming::DEFAULT_MAX_RESPONSE_SIZE,
+//! )
+//! .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{Error, Response, Transport, Url};
+use bytes::Bytes;
+use futures_util::TryStreamExt;
+use serde::de::DeserializeOwned;
+use std::{
+    fmt, io,
+    io::Read,
+    sync::{Arc, Mutex},
+};
+use tokio::io::AsyncRead;
+use tokio_util::io::{StreamReader, SyncIoBridge};
+
+/// The default limit for the size of a response body: 64 MiB.
+pub const DEFAULT_MAX_RESPONSE_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Sends a request through the given transport and incrementally deserializes the result while the response arrives.
+///
+/// The response is received through [`Transport::send_streaming`].
+/// Fails with [`Error::ResponseTooLarge`] if the body of the response is larger than `max_response_size` bytes.
+pub async fn send<T, P>(
+    transport: &T,
+    target: &Url,
+    request: Bytes,
+    max_response_size: u64,
+) -> Result<P, Error<T::Error>>
+where
+    T: Transport + Sync,
+    T::Error: Send + 'static,
+    P: DeserializeOwned + Send + 'static,
+{
+    let body = transport
+        .send_streaming(target, request)
+        .await
+        .map_err(Error::Client)?;
+
+    // `StreamReader` only deals with `io::Error`s, keep the error of the transport around to return it as is
+    let transport_error = Arc::new(Mutex::new(None));
+    let body = StreamReader::new(body.map_err({
+        let transport_error = transport_error.clone();
+
+        move |e| {
+            *transport_error.lock().unwrap() = Some(e);
+
+            io::Error::other("failed to receive response")
+        }
+    }));
+
+    match read_response::<_, P>(body, max_response_size).await {
+        Ok(response) => Ok(Result::from(response.payload)?),
+        Err(ReadError::TooLarge(max_size)) => Err(Error::ResponseTooLarge(max_size)),
+        Err(ReadError::Json(e)) => Err(Error::Serde(e)),
+        Err(ReadError::Io(e)) => match transport_error.lock().unwrap().take() {
+            Some(transport_error) => Err(Error::Client(transport_error)),
+            None => Err(Error::Serde(serde_json::Error::io(e))),
+        },
+    }
+}
+
+/// Incrementally deserializes a response from the given body.
+///
+/// Fails with [`ReadError::TooLarge`] if the body is larger than `max_size` bytes.
+pub async fn read_response<R, P>(body: R, max_size: u64) -> Result<Response<P>, ReadError>
+where
+    R: AsyncRead + Send + Unpin + 'static,
+    P: DeserializeOwned + Send + 'static,
+{
+    let body = SyncIoBridge::new(body);
+
+    let result = tokio::task::spawn_blocking(move || {
+        let body = io::BufReader::new(Limited {
+            inner: body,
+            remaining: max_size,
+        });
+
+        serde_json::from_reader::<_, Response<P>>(body)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(response)) => Ok(response),
+        Ok(Err(e)) if e.is_io() => {
+            let e = io::Error::from(e);
+
+            if e.get_ref().is_some_and(|inner| inner.is::<TooLarge>()) {
+                Err(ReadError::TooLarge(max_size))
+            } else {
+                Err(ReadError::Io(e))
+            }
+        }
+        Ok(Err(e)) => Err(ReadError::Json(e)),
+        Err(e) => std::panic::resume_unwind(e.into_panic()),
+    }
+}
+
+/// Errors that can occur while reading a response with [`read_response`].
+#[derive(Debug)]
+pub enum ReadError {
+    /// Reading the body failed.
+    Io(io::Error),
+    /// The body was larger than the given number of bytes.
+    TooLarge(u64),
+    /// The body is not a valid response.
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadError::Io(_) => write!(f, "failed to read response"),
+            ReadError::TooLarge(max_size) => {
+                write!(f, "response exceeds the limit of {} bytes", max_size)
+            }
+            ReadError::Json(_) => write!(f, "failed to deserialize response"),
+        }
+    }
+}
+
+impl std::error::Error for ReadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ReadError::Io(inner) => Some(inner),
+            ReadError::TooLarge(_) => None,
+            ReadError::Json(inner) => Some(inner),
+        }
+    }
+}
+
+/// A reader that fails once more than the given number of bytes have been read.
+struct Limited<R> {
+    inner: R,
+    remaining: u64,
+}
+
+impl<R> Read for Limited<R>
+where
+    R: Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        // read one byte past the limit to tell a body of exactly the maximum size from a larger one
+        let max = buf.len().min(self.remaining.saturating_add(1) as usize);
+        let read = self.inner.read(&mut buf[..max])?;
+
+        if read as u64 > self.remaining {
+            return Err(io::Error::other(TooLarge));
+        }
+        self.remaining -= read as u64;
+
+        Ok(read)
+    }
+}
+
+#[derive(Debug)]
+struct TooLarge;
+
+impl fmt::Display for TooLarge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("response too large")
+    }
+}
+
+impl std::error::Error for TooLarge {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn reads_response_within_limit() {
+        let body = br#"{"jsonrpc":"2.0","id":1,"result":[1,2,3]}"#;
+
+        let response = read_response::<_, Vec<u64>>(&body[..], body.len() as u64)
+            .await
+            .unwrap();
+
+        assert_eq!(Result::from(response.payload), Ok(vec![1, 2, 3]));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn fails_on_response_above_limit() {
+        let body = br#"{"jsonrpc":"2.0","id":1,"result":[1,2,3]}"#;
+
+        let error = read_response::<_, Vec<u64>>(&body[..], body.len() as u64 - 1)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, ReadError::TooLarge(_)));
+    }
+}
@@ -1,5 +1,5 @@
-use crate::{Response, SendRequest, Url};
-use serde::de::DeserializeOwned;
+use crate::{Transport, Url};
+use bytes::Bytes;
 use std::fmt;
 use surf::http::Method;
 
@@ -21,21 +21,21 @@ impl fmt::Display for Error {
 }
 
 #[async_trait::async_trait]
-impl SendRequest for surf::Client {
+impl Transport for surf::Client {
     type Error = Error;
 
-    async fn send_request<P>(&self, endpoint: Url, body: String) -> Result<Response<P>, Self::Error>
-    where
-        P: DeserializeOwned,
-    {
-        let request = surf::Request::builder(Method::Post, endpoint)
-            .body(body)
+    async fn send(&self, target: &Url, body: Bytes) -> Result<Bytes, Self::Error> {
+        let request = surf::Request::builder(Method::Post, target.clone())
+            .body(body.as_ref())
             .header("Content-type", "application/json")
             .build();
 
-        let response = self.send(request).await?.body_json().await?;
+        let response = surf::Client::send(self, request)
+            .await?
+            .body_bytes()
+            .await?;
 
-        Ok(response)
+        Ok(Bytes::from(response))
     }
 }
 
@@ -0,0 +1,45 @@
+//! Newline-delimited JSON-RPC over raw TCP sockets.
+//!
+//! This is the transport spoken by Electrum servers and many embedded devices.
+//! Endpoints are addressed through URLs of the form `tcp://host:port`.
+//!
+//! To connect over TLS, establish the TLS stream yourself and hand it to [`StreamClient::new`] together with a [`NewlineCodec`].
+
+use crate::{
+    codec::{Codec, NewlineCodec},
+    stream::{Error, StreamClient},
+    Url,
+};
+use tokio::net::TcpStream;
+use url::Host;
+
+/// Connects to the given `tcp://host:port` URL, framing messages with newlines.
+pub async fn connect(url: &Url) -> Result<StreamClient, Error> {
+    connect_with_codec(url, NewlineCodec::default()).await
+}
+
+/// Connects to the given `tcp://host:port` URL, framing messages with the given codec.
+pub async fn connect_with_codec<C>(url: &Url, codec: C) -> Result<StreamClient, Error>
+where
+    C: Codec,
+{
+    let unsupported_url = || Error::UnsupportedUrl(url.clone());
+
+    if url.scheme() != "tcp" {
+        return Err(unsupported_url());
+    }
+
+    let host = match url.host().ok_or_else(unsupported_url)? {
+        Host::Domain(domain) => domain.to_owned(),
+        Host::Ipv4(address) => address.to_string(),
+        Host::Ipv6(address) => address.to_string(),
+    };
+    let port = url.port().ok_or_else(unsupported_url)?;
+
+    let stream = TcpStream::connect((host.as_str(), port)).await?;
+    stream.set_nodelay(true)?;
+
+    let (reader, writer) = stream.into_split();
+
+    Ok(StreamClient::from_parts(reader, writer, codec))
+}
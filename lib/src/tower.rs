@@ -0,0 +1,187 @@
+//! Adapters between [`Transport`] and [`tower_service::Service`].
+//!
+//! [`ServiceTransport`] sends requests through any service of `http::Request<Bytes>`, for example an HTTP client that is wrapped in tower layers for timeouts or retries.
+//! [`JsonRpcService`] goes the other way and exposes a [`Transport`] implementation as a service of [`Request`]s so tower layers can wrap it.
+
+use crate::{Request, Response, Transport, Url};
+use bytes::Bytes;
+use futures_util::future::poll_fn;
+use http::header::CONTENT_TYPE;
+use serde_json::Value;
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+use tower_service::Service;
+
+/// The error type that tower middleware commonly uses.
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Sends requests through a [`Service`] of `http::Request<Bytes>`.
+///
+/// The service is cloned for every request, as is customary for tower services.
+/// It doesn't need to be `Sync`, which allows using type-erased stacks like `tower::util::BoxCloneService`.
+#[derive(Debug)]
+pub struct ServiceTransport<S> {
+    service: Mutex<S>,
+}
+
+impl<S> ServiceTransport<S> {
+    /// Wraps the given service, which is usually an HTTP client with layers applied to it.
+    pub fn new(service: S) -> Self {
+        Self {
+            service: Mutex::new(service),
+        }
+    }
+
+    /// Returns the wrapped service.
+    pub fn into_inner(self) -> S {
+        self.service
+            .into_inner()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl<S> Clone for ServiceTransport<S>
+where
+    S: Clone,
+{
+    fn clone(&self) -> Self {
+        Self::new(self.clone_service())
+    }
+}
+
+impl<S> ServiceTransport<S>
+where
+    S: Clone,
+{
+    fn clone_service(&self) -> S {
+        self.service
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl<S> Transport for ServiceTransport<S>
+where
+    S: Service<http::Request<Bytes>, Response = http::Response<Bytes>> + Clone + Send + 'static,
+    S::Error: Into<BoxError>,
+    S::Future: Send,
+{
+    type Error = Error;
+
+    async fn send(&self, target: &Url, body: Bytes) -> Result<Bytes, Self::Error> {
+        let request = http::Request::post(target.as_str())
+            .header(CONTENT_TYPE, "application/json")
+            .body(body)?;
+
+        let mut service = self.clone_service();
+        poll_fn(|cx| service.poll_ready(cx))
+            .await
+            .map_err(|e| Error::Service(e.into()))?;
+        let response = service
+            .call(request)
+            .await
+            .map_err(|e| Error::Service(e.into()))?;
+
+        Ok(response.into_body())
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Service(BoxError),
+    Http(http::Error),
+}
+
+impl From<http::Error> for Error {
+    fn from(e: http::Error) -> Self {
+        Error::Http(e)
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Service(inner) => Some(inner.as_ref()),
+            Error::Http(inner) => Some(inner),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Service(inner) => fmt::Display::fmt(inner, f),
+            Error::Http(inner) => fmt::Display::fmt(inner, f),
+        }
+    }
+}
+
+impl From<Error> for crate::Error<Error> {
+    fn from(inner: Error) -> Self {
+        crate::Error::Client(inner)
+    }
+}
+
+/// A [`Service`] that sends [`Request`]s to a fixed endpoint through a [`Transport`].
+///
+/// The result of the response is left as a [`Value`] for the caller to deserialize.
+#[derive(Debug)]
+pub struct JsonRpcService<C> {
+    client: Arc<C>,
+    endpoint: Url,
+}
+
+impl<C> JsonRpcService<C> {
+    /// Creates a service that sends all requests through `client` to `endpoint`.
+    pub fn new(client: C, endpoint: Url) -> Self {
+        Self {
+            client: Arc::new(client),
+            endpoint,
+        }
+    }
+}
+
+impl<C> Clone for JsonRpcService<C> {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client.clone(),
+            endpoint: self.endpoint.clone(),
+        }
+    }
+}
+
+impl<C> Service<Request> for JsonRpcService<C>
+where
+    C: Transport + Send + Sync,
+{
+    type Response = Response<Value>;
+    type Error = crate::Error<C::Error>;
+    #[allow(clippy::type_complexity)]
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let client = self.client.clone();
+        let endpoint = self.endpoint.clone();
+
+        Box::pin(async move {
+            let body = request.serialize()?;
+            let response = client
+                .send(&endpoint, Bytes::from(body))
+                .await
+                .map_err(crate::Error::Client)?;
+
+            Ok(serde_json::from_slice(&response)?)
+        })
+    }
+}
@@ -0,0 +1,58 @@
+//! JSON-RPC over Unix domain sockets.
+//!
+//! This is the transport used by the RPC socket of Core Lightning.
+//! Endpoints are addressed through URLs of the form `unix:///path/to/socket`.
+//!
+//! By default, messages are framed with the [`JsonStreamCodec`], which also decodes newline-delimited messages.
+
+use crate::{
+    codec::{Codec, JsonStreamCodec},
+    stream::{Error, StreamClient},
+    Url,
+};
+use percent_encoding::{percent_decode_str, percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use std::{ffi::OsStr, io, os::unix::ffi::OsStrExt, path::Path};
+use tokio::net::UnixStream;
+
+/// Bytes that are kept verbatim in the path of a `unix://` URL.
+const PATH: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'/')
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+/// Builds the `unix:///path/to/socket` URL of the socket at the given path.
+///
+/// Relative paths are resolved against the current directory, hence the socket has to exist.
+/// Characters like `#`, `?` or `%` are percent-encoded, so they survive the round-trip through [`connect`].
+pub fn url_from_path(path: impl AsRef<Path>) -> io::Result<Url> {
+    let path = std::fs::canonicalize(path)?;
+    let path = percent_encode(path.as_os_str().as_bytes(), PATH);
+
+    format!("unix://{}", path)
+        .parse()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+}
+
+/// Connects to the socket at the given `unix:///path/to/socket` URL, expecting concatenated JSON messages.
+pub async fn connect(url: &Url) -> Result<StreamClient, Error> {
+    connect_with_codec(url, JsonStreamCodec::default()).await
+}
+
+/// Connects to the socket at the given `unix:///path/to/socket` URL, framing messages with the given codec.
+pub async fn connect_with_codec<C>(url: &Url, codec: C) -> Result<StreamClient, Error>
+where
+    C: Codec,
+{
+    if url.scheme() != "unix" || matches!(url.host_str(), Some(host) if !host.is_empty()) {
+        return Err(Error::UnsupportedUrl(url.clone()));
+    }
+
+    let path = percent_decode_str(url.path()).collect::<Vec<_>>();
+    let stream = UnixStream::connect(OsStr::from_bytes(&path)).await?;
+
+    let (reader, writer) = stream.into_split();
+
+    Ok(StreamClient::from_parts(reader, writer, codec))
+}
@@ -0,0 +1,70 @@
+use crate::{TransportBlocking, Url};
+use bytes::Bytes;
+use std::{
+    fmt,
+    io::{self, Read},
+};
+
+#[derive(Debug)]
+pub enum Error {
+    Transport(Box<ureq::Transport>),
+    Io(io::Error),
+}
+
+impl From<ureq::Transport> for Error {
+    fn from(e: ureq::Transport) -> Self {
+        Error::Transport(Box::new(e))
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Transport(inner) => Some(inner.as_ref()),
+            Error::Io(inner) => Some(inner),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Transport(inner) => fmt::Display::fmt(inner, f),
+            Error::Io(inner) => fmt::Display::fmt(inner, f),
+        }
+    }
+}
+
+impl TransportBlocking for ureq::Agent {
+    type Error = Error;
+
+    fn send(&self, target: &Url, body: Bytes) -> Result<Bytes, Self::Error> {
+        let response = match self
+            .post(target.as_str())
+            .set("Content-Type", "application/json")
+            .send_bytes(&body)
+        {
+            Ok(response) => response,
+            // JSON-RPC servers may send errors with an HTTP error status
+            Err(ureq::Error::Status(_, response)) => response,
+            Err(ureq::Error::Transport(transport)) => return Err(transport.into()),
+        };
+
+        let mut body = Vec::new();
+        response.into_reader().read_to_end(&mut body)?;
+
+        Ok(Bytes::from(body))
+    }
+}
+
+impl From<Error> for crate::Error<Error> {
+    fn from(inner: Error) -> Self {
+        crate::Error::Client(inner)
+    }
+}
@@ -0,0 +1,110 @@
+#![cfg(all(feature = "macros", feature = "awc"))]
+
+use awc::error::PayloadError;
+use hyper::{
+    header::AUTHORIZATION,
+    service::{make_service_fn, service_fn},
+    Body, Request, Server,
+};
+use jsonrpc_client::Url;
+use serde_json::{json, Value};
+use std::{convert::Infallible, net::SocketAddr};
+
+#[jsonrpc_client::api(local)]
+pub trait Math {
+    async fn subtract(&self, subtrahend: i64, minuend: i64) -> i64;
+}
+
+#[jsonrpc_client::implement_local(MathLocal)]
+struct Client {
+    inner: awc::Client,
+    base_url: Url,
+}
+
+#[jsonrpc_client::implement_local(MathLocal)]
+struct LimitedClient {
+    inner: jsonrpc_client::awc::Client,
+    base_url: Url,
+}
+
+async fn subtract(request: Request<Body>) -> Result<hyper::Response<Body>, Infallible> {
+    let authorization = request.headers().get(AUTHORIZATION).cloned();
+    let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+    let request = serde_json::from_slice::<Value>(&body).unwrap();
+    let params = &request["params"];
+
+    let response = match authorization {
+        // base64 of `user:p@ss`
+        Some(authorization) if authorization != "Basic dXNlcjpwQHNz" => json!({
+            "jsonrpc": "2.0",
+            "id": request["id"],
+            "error": { "code": -32600, "message": "unauthorized" }
+        }),
+        _ => json!({
+            "jsonrpc": "2.0",
+            "id": request["id"],
+            "result": params["subtrahend"].as_i64().unwrap() - params["minuend"].as_i64().unwrap()
+        }),
+    };
+
+    Ok(hyper::Response::new(Body::from(response.to_string())))
+}
+
+fn start_server() -> SocketAddr {
+    let make_service = make_service_fn(|_| async { Ok::<_, Infallible>(service_fn(subtract)) });
+    let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+    let address = server.local_addr();
+
+    tokio::spawn(server);
+
+    address
+}
+
+#[actix_rt::test]
+async fn sends_requests_on_single_thread() {
+    let address = start_server();
+    let client = Client {
+        inner: awc::Client::default(),
+        base_url: format!("http://{}/", address).parse().unwrap(),
+    };
+
+    let result = client.subtract(10, 5).await.unwrap();
+
+    assert_eq!(result, 5);
+}
+
+#[actix_rt::test]
+async fn sends_credentials_of_url() {
+    let address = start_server();
+    let client = Client {
+        inner: awc::Client::default(),
+        base_url: format!("http://user:p%40ss@{}/", address).parse().unwrap(),
+    };
+
+    let wrong_client = Client {
+        inner: awc::Client::default(),
+        base_url: format!("http://user:wrong@{}/", address).parse().unwrap(),
+    };
+
+    let result = client.subtract(10, 5).await.unwrap();
+    let error = wrong_client.subtract(10, 5).await.unwrap_err();
+
+    assert_eq!(result, 5);
+    assert!(matches!(error, jsonrpc_client::Error::JsonRpc(error) if error.code == -32600));
+}
+
+#[actix_rt::test]
+async fn rejects_responses_above_limit() {
+    let address = start_server();
+    let client = LimitedClient {
+        inner: jsonrpc_client::awc::Client::new(awc::Client::default(), 8),
+        base_url: format!("http://{}/", address).parse().unwrap(),
+    };
+
+    let error = client.subtract(10, 5).await.unwrap_err();
+
+    assert!(matches!(
+        error,
+        jsonrpc_client::Error::Client(jsonrpc_client::awc::Error::Payload(PayloadError::Overflow))
+    ));
+}
@@ -0,0 +1,135 @@
+#![cfg(all(feature = "macros", feature = "http-unix", unix))]
+
+use hyper::{server::conn::Http, service::service_fn, Body, Request};
+use jsonrpc_client::{http_unix, Url};
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use serde_json::{json, Value};
+use std::{
+    convert::Infallible,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+use tokio::net::UnixListener;
+
+#[jsonrpc_client::api]
+pub trait Math {
+    async fn subtract(&self, subtrahend: i64, minuend: i64) -> i64;
+}
+
+#[jsonrpc_client::implement(Math)]
+struct Client {
+    inner: http_unix::Client,
+    base_url: Url,
+}
+
+fn socket_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "jsonrpc-client-http-{}-{} rpc.sock",
+        name,
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+
+    path
+}
+
+fn endpoint(path: &Path, rpc_path: &str) -> Url {
+    let host = utf8_percent_encode(path.to_str().unwrap(), NON_ALPHANUMERIC);
+
+    format!("http+unix://{}{}", host, rpc_path).parse().unwrap()
+}
+
+async fn subtract(request: Request<Body>) -> Result<hyper::Response<Body>, Infallible> {
+    let path = request.uri().path().to_owned();
+    let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+    let request = serde_json::from_slice::<Value>(&body).unwrap();
+    let params = &request["params"];
+
+    let response = match path.as_str() {
+        "/rpc" => json!({
+            "jsonrpc": "2.0",
+            "id": request["id"],
+            "result": params["subtrahend"].as_i64().unwrap() - params["minuend"].as_i64().unwrap()
+        }),
+        _ => json!({
+            "jsonrpc": "2.0",
+            "id": request["id"],
+            "error": { "code": -32601, "message": "not found" }
+        }),
+    };
+
+    Ok(hyper::Response::new(Body::from(response.to_string())))
+}
+
+/// Starts a server on the given socket that answers `subtract` requests on `/rpc` and counts the connections made to it.
+fn start_server(path: &Path) -> Arc<AtomicUsize> {
+    let listener = UnixListener::bind(path).unwrap();
+    let connections = Arc::new(AtomicUsize::new(0));
+
+    tokio::spawn({
+        let connections = connections.clone();
+
+        async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                connections.fetch_add(1, Ordering::SeqCst);
+
+                tokio::spawn(Http::new().serve_connection(stream, service_fn(subtract)));
+            }
+        }
+    });
+
+    connections
+}
+
+#[tokio::test]
+async fn sends_requests_to_socket_path() {
+    let path = socket_path("pooled");
+    let connections = start_server(&path);
+    let client = Client {
+        inner: http_unix::Client::new(),
+        base_url: endpoint(&path, "/rpc"),
+    };
+
+    let first = client.subtract(10, 5).await.unwrap();
+    let second = client.subtract(3, 1).await.unwrap();
+
+    assert_eq!(first, 5);
+    assert_eq!(second, 2);
+    assert_eq!(connections.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn sends_requests_to_http_path() {
+    let path = socket_path("path");
+    start_server(&path);
+    let client = Client {
+        inner: http_unix::Client::new(),
+        base_url: endpoint(&path, "/other"),
+    };
+
+    let error = client.subtract(10, 5).await.unwrap_err();
+
+    assert!(matches!(
+        error,
+        jsonrpc_client::Error::JsonRpc(jsonrpc_client::JsonRpcError { code: -32601, .. })
+    ));
+}
+
+#[tokio::test]
+async fn rejects_other_schemes() {
+    let client = Client {
+        inner: http_unix::Client::new(),
+        base_url: "http://localhost/rpc".parse().unwrap(),
+    };
+
+    let error = client.subtract(10, 5).await.unwrap_err();
+
+    assert!(matches!(
+        error,
+        jsonrpc_client::Error::Client(jsonrpc_client::hyper::Error::Hyper(_))
+    ));
+}
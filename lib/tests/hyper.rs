@@ -0,0 +1,105 @@
+#![cfg(all(feature = "macros", feature = "hyper"))]
+
+use hyper::{
+    client::HttpConnector,
+    header::AUTHORIZATION,
+    service::{make_service_fn, service_fn},
+    Body, Request, Server,
+};
+use jsonrpc_client::Url;
+use serde_json::{json, Value};
+use std::{
+    convert::Infallible,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+#[jsonrpc_client::api]
+pub trait Math {
+    async fn subtract(&self, subtrahend: i64, minuend: i64) -> i64;
+}
+
+#[jsonrpc_client::implement(Math)]
+struct Client {
+    inner: hyper::Client<HttpConnector, Body>,
+    base_url: Url,
+}
+
+async fn subtract(request: Request<Body>) -> Result<hyper::Response<Body>, Infallible> {
+    let authorization = request.headers().get(AUTHORIZATION).cloned();
+    let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+    let request = serde_json::from_slice::<Value>(&body).unwrap();
+    let params = &request["params"];
+
+    let response = match authorization {
+        // base64 of `user:p@ss`
+        Some(authorization) if authorization == "Basic dXNlcjpwQHNz" => json!({
+            "jsonrpc": "2.0",
+            "id": request["id"],
+            "result": params["subtrahend"].as_i64().unwrap() - params["minuend"].as_i64().unwrap()
+        }),
+        _ => json!({
+            "jsonrpc": "2.0",
+            "id": request["id"],
+            "error": { "code": -32600, "message": "unauthorized" }
+        }),
+    };
+
+    Ok(hyper::Response::new(Body::from(response.to_string())))
+}
+
+/// Starts a server that answers `subtract` requests and counts the connections made to it.
+fn start_server() -> (SocketAddr, Arc<AtomicUsize>) {
+    let connections = Arc::new(AtomicUsize::new(0));
+
+    let make_service = make_service_fn({
+        let connections = connections.clone();
+
+        move |_| {
+            connections.fetch_add(1, Ordering::SeqCst);
+
+            async { Ok::<_, Infallible>(service_fn(subtract)) }
+        }
+    });
+    let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+    let address = server.local_addr();
+
+    tokio::spawn(server);
+
+    (address, connections)
+}
+
+#[tokio::test]
+async fn sends_requests_over_pooled_connection() {
+    let (address, connections) = start_server();
+    let client = Client {
+        inner: hyper::Client::new(),
+        base_url: format!("http://user:p%40ss@{}/", address).parse().unwrap(),
+    };
+
+    let first = client.subtract(10, 5).await.unwrap();
+    let second = client.subtract(3, 1).await.unwrap();
+
+    assert_eq!(first, 5);
+    assert_eq!(second, 2);
+    assert_eq!(connections.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn sends_requests_without_credentials() {
+    let (address, _) = start_server();
+    let client = Client {
+        inner: hyper::Client::new(),
+        base_url: format!("http://{}/", address).parse().unwrap(),
+    };
+
+    let error = client.subtract(10, 5).await.unwrap_err();
+
+    assert!(matches!(
+        error,
+        jsonrpc_client::Error::JsonRpc(jsonrpc_client::JsonRpcError { code: -32600, .. })
+    ));
+}
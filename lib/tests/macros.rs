@@ -1,8 +1,22 @@
 #![cfg(feature = "macros")]
 
-use jsonrpc_client::{Error, Id, Response, SendRequest, Url};
+use bytes::Bytes;
+use futures_util::TryStreamExt;
+use jsonrpc_client::{
+    DynTransport, Endpoint, Error, Id, Lazy, RawValue, Response, SendRequest, SendRequestBlocking,
+    SendRequestLocal, Transport, TransportMut, Url,
+};
 use serde::{de::DeserializeOwned, Serialize};
-use std::{cell::Cell, fmt, ops::Deref};
+use std::{
+    borrow::Cow,
+    cell::Cell,
+    fmt,
+    ops::Deref,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
 
 #[jsonrpc_client::api(version = "1.0")]
 pub trait MathV1 {
@@ -19,6 +33,32 @@ pub trait MathV2Default {
     async fn subtract(&self, subtrahend: i64, minuend: i64) -> i64;
 }
 
+#[jsonrpc_client::api(version = "2.0", blocking, local)]
+pub trait Calculator {
+    async fn subtract(&self, subtrahend: i64, minuend: i64) -> i64;
+
+    async fn negate(&self, value: i64) -> Result<i64, Error<Self::Error>> {
+        self.subtract(0, value).await
+    }
+}
+
+#[jsonrpc_client::api(native)]
+pub trait MathNative {
+    async fn subtract(&self, subtrahend: i64, minuend: i64) -> i64;
+    async fn sum(&self, summands: &[i64]) -> i64;
+}
+
+#[jsonrpc_client::api(version = "2.0")]
+pub trait Blocks {
+    async fn getblock(&self, hash: String) -> Box<RawValue>;
+    async fn getblockheader(&self, hash: String) -> Lazy<BlockHeader>;
+}
+
+#[derive(serde::Deserialize, Debug, PartialEq)]
+pub struct BlockHeader {
+    height: u64,
+}
+
 #[derive(Default)]
 pub struct InnerClient {
     next_response: Cell<Option<String>>,
@@ -69,9 +109,138 @@ impl SendRequest for InnerClient {
     }
 }
 
-impl From<DummyError> for Error<DummyError> {
-    fn from(inner: DummyError) -> Self {
-        Error::Client(inner)
+impl SendRequestBlocking for InnerClient {
+    type Error = DummyError;
+
+    fn send_request<P>(&self, _: Url, request: String) -> Result<Response<P>, Self::Error>
+    where
+        P: DeserializeOwned,
+    {
+        self.recorded_request.set(Some(request));
+        let response = self.next_response.replace(None).unwrap();
+
+        Ok(serde_json::from_str(&response).unwrap())
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl SendRequestLocal for InnerClient {
+    type Error = DummyError;
+
+    async fn send_request<P>(&self, _: Url, request: String) -> Result<Response<P>, Self::Error>
+    where
+        P: DeserializeOwned,
+    {
+        self.recorded_request.set(Some(request));
+        let response = self.next_response.replace(None).unwrap();
+
+        Ok(serde_json::from_str(&response).unwrap())
+    }
+}
+
+/// A transport that answers every request with the same bytes.
+pub struct StaticTransport(&'static str);
+
+impl Default for StaticTransport {
+    fn default() -> Self {
+        Self(r#"{"jsonrpc":"2.0","id":0,"result":1}"#)
+    }
+}
+
+#[async_trait::async_trait]
+impl Transport for StaticTransport {
+    type Error = DummyError;
+
+    async fn send(&self, _: &Url, _: Bytes) -> Result<Bytes, Self::Error> {
+        Ok(Bytes::from_static(self.0.as_bytes()))
+    }
+}
+
+/// A transport that answers every request with the same bytes and records the targets of the requests.
+#[derive(Default)]
+pub struct RecordingTransport {
+    targets: std::sync::Mutex<Vec<String>>,
+}
+
+#[async_trait::async_trait]
+impl Transport for RecordingTransport {
+    type Error = DummyError;
+
+    async fn send(&self, target: &Url, body: Bytes) -> Result<Bytes, Self::Error> {
+        self.targets.lock().unwrap().push(target.to_string());
+
+        Transport::send(&StaticTransport::default(), target, body).await
+    }
+}
+
+/// A transport that fails every request.
+pub struct FailingTransport;
+
+#[async_trait::async_trait]
+impl Transport for FailingTransport {
+    type Error = DummyError;
+
+    async fn send(&self, _: &Url, _: Bytes) -> Result<Bytes, Self::Error> {
+        Err(DummyError)
+    }
+}
+
+/// A transport that needs exclusive access, answering every request with the number of requests it has sent.
+#[derive(Default)]
+pub struct Session {
+    requests_sent: i64,
+}
+
+#[async_trait::async_trait]
+impl TransportMut for Session {
+    type Error = DummyError;
+
+    async fn send(&mut self, _: &Url, _: Bytes) -> Result<Bytes, Self::Error> {
+        let requests_sent = self.requests_sent + 1;
+        tokio::task::yield_now().await;
+        self.requests_sent = requests_sent;
+
+        let response = Response::new_v2_result(Id::Number(0), requests_sent);
+
+        Ok(serde_json::to_vec(&response).unwrap().into())
+    }
+}
+
+/// An implementation of the API that doesn't send any requests, like one that replaces a client in tests.
+pub struct MockMath;
+
+#[async_trait::async_trait]
+impl MathV2 for MockMath {
+    type Error = DummyError;
+
+    async fn subtract(&self, subtrahend: i64, minuend: i64) -> Result<i64, Error<DummyError>> {
+        Ok(subtrahend - minuend)
+    }
+}
+
+/// An endpoint that alternates between two servers.
+pub struct RoundRobin {
+    urls: [Url; 2],
+    next: AtomicUsize,
+}
+
+impl Default for RoundRobin {
+    fn default() -> Self {
+        Self {
+            urls: [
+                "http://example.org/".parse().unwrap(),
+                "http://example.com/".parse().unwrap(),
+            ],
+            next: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl Endpoint for RoundRobin {
+    fn url(&self) -> Cow<'_, Url> {
+        let next = self.next.fetch_add(1, Ordering::Relaxed);
+
+        Cow::Borrowed(&self.urls[next % self.urls.len()])
     }
 }
 
@@ -91,21 +260,9 @@ impl Deref for ExampleDotOrg {
     }
 }
 
-fn assert_impls_math_v1<C: SendRequest, T: MathV1<C>>(_: T)
-where
-    Error<<C as SendRequest>::Error>: From<<C as SendRequest>::Error>,
-{
-}
-fn assert_impls_math_v2<C: SendRequest, T: MathV2<C>>(_: T)
-where
-    Error<<C as SendRequest>::Error>: From<<C as SendRequest>::Error>,
-{
-}
-fn assert_impls_math_v2_default<C: SendRequest, T: MathV2Default<C>>(_: T)
-where
-    Error<<C as SendRequest>::Error>: From<<C as SendRequest>::Error>,
-{
-}
+fn assert_impls_math_v1(_: impl MathV1) {}
+fn assert_impls_math_v2(_: impl MathV2) {}
+fn assert_impls_math_v2_default(_: impl MathV2Default) {}
 
 mod derive_on_named_inner {
     use crate::{ExampleDotOrg, InnerClient};
@@ -132,6 +289,190 @@ mod derive_on_named_inner_multiple_fields {
     }
 }
 
+mod derive_blocking_on_named_inner {
+    use crate::{ExampleDotOrg, InnerClient};
+
+    #[jsonrpc_client::implement(super::Calculator)]
+    #[jsonrpc_client::implement_blocking(super::CalculatorBlocking)]
+    #[jsonrpc_client::implement_local(super::CalculatorLocal)]
+    #[derive(Default)]
+    pub struct Client {
+        pub inner: InnerClient,
+        pub base_url: ExampleDotOrg,
+    }
+}
+
+mod derive_on_transport {
+    use crate::{ExampleDotOrg, StaticTransport};
+
+    #[jsonrpc_client::implement(super::MathV2)]
+    #[jsonrpc_client::implement(super::MathNative)]
+    #[derive(Default)]
+    pub struct Client {
+        pub inner: StaticTransport,
+        pub base_url: ExampleDotOrg,
+    }
+}
+
+mod derive_blocks_on_transport {
+    use crate::{ExampleDotOrg, StaticTransport};
+
+    #[jsonrpc_client::implement(super::Blocks)]
+    #[derive(Default)]
+    pub struct Client {
+        pub inner: StaticTransport,
+        pub base_url: ExampleDotOrg,
+    }
+}
+
+mod derive_on_exclusive_transport {
+    use crate::{ExampleDotOrg, Session};
+    use jsonrpc_client::Exclusive;
+
+    #[jsonrpc_client::implement(super::MathV2)]
+    #[derive(Default)]
+    pub struct Client {
+        pub inner: Exclusive<Session>,
+        pub base_url: ExampleDotOrg,
+    }
+}
+
+mod derive_on_generic_client {
+    use jsonrpc_client::Url;
+
+    #[jsonrpc_client::implement(super::MathV2)]
+    #[jsonrpc_client::implement_blocking(super::CalculatorBlocking)]
+    pub struct Client<'a, T>
+    where
+        T: Send,
+    {
+        pub inner: T,
+        pub base_url: &'a Url,
+    }
+}
+
+mod derive_multiple_traits_with_paths {
+    use crate::{ExampleDotOrg, RecordingTransport};
+
+    #[jsonrpc_client::implement(
+        super::MathV1(path = "v1"),
+        super::MathV2,
+        super::MathNative(path = "/native/")
+    )]
+    #[derive(Default)]
+    pub struct Client {
+        pub inner: RecordingTransport,
+        pub base_url: ExampleDotOrg,
+    }
+}
+
+mod derive_on_dyn_transport {
+    use crate::ExampleDotOrg;
+    use jsonrpc_client::DynTransport;
+    use std::sync::Arc;
+
+    #[jsonrpc_client::implement(super::MathV2)]
+    pub struct Client {
+        pub inner: Arc<dyn DynTransport>,
+        pub base_url: ExampleDotOrg,
+    }
+}
+
+mod derive_on_enum {
+    use crate::{ExampleDotOrg, FailingTransport, RecordingTransport, RoundRobin, StaticTransport};
+    use jsonrpc_client::Url;
+
+    #[jsonrpc_client::implement(super::MathV2, super::MathV1(path = "v1"))]
+    pub enum Client {
+        Static {
+            inner: StaticTransport,
+            base_url: ExampleDotOrg,
+        },
+        Recording(
+            #[jsonrpc_client(base_url)] Url,
+            #[jsonrpc_client(inner)] RecordingTransport,
+        ),
+        Failing {
+            inner: FailingTransport,
+            base_url: ExampleDotOrg,
+        },
+        Balanced {
+            inner: RecordingTransport,
+            base_url: RoundRobin,
+        },
+    }
+}
+
+mod derive_on_endpoint {
+    use crate::RecordingTransport;
+
+    #[jsonrpc_client::implement(super::MathV2, super::MathV1(path = "v1"))]
+    #[derive(Default)]
+    pub struct Client<E> {
+        pub inner: RecordingTransport,
+        pub base_url: E,
+    }
+}
+
+mod derive_on_endpoint_method {
+    use crate::RecordingTransport;
+    use jsonrpc_client::Url;
+
+    #[jsonrpc_client::implement(super::MathV2)]
+    #[jsonrpc_client(base_url = "Self::endpoint")]
+    #[derive(Default)]
+    pub struct Client {
+        pub inner: RecordingTransport,
+        pub shard: u32,
+    }
+
+    impl Client {
+        fn endpoint(&self) -> Url {
+            format!("http://shard-{}.example.org/", self.shard)
+                .parse()
+                .unwrap()
+        }
+    }
+}
+
+mod derive_stacked_on_tagged_fields {
+    use crate::InnerClient;
+    use jsonrpc_client::Url;
+
+    #[jsonrpc_client::implement(super::Calculator)]
+    #[jsonrpc_client::implement_blocking(super::CalculatorBlocking)]
+    #[jsonrpc_client(base_url = "Self::endpoint")]
+    #[derive(Default)]
+    pub struct Client {
+        #[jsonrpc_client(inner)]
+        pub transport: InnerClient,
+        pub shard: u32,
+    }
+
+    impl Client {
+        fn endpoint(&self) -> Url {
+            format!("http://shard-{}.example.org/", self.shard)
+                .parse()
+                .unwrap()
+        }
+    }
+}
+
+mod derive_stacked_with_imported_macros {
+    use crate::{ExampleDotOrg, InnerClient};
+    use jsonrpc_client::{implement, implement_blocking};
+
+    #[implement(super::Calculator)]
+    #[implement_blocking(super::CalculatorBlocking)]
+    #[derive(Default)]
+    pub struct Client {
+        #[jsonrpc_client(inner)]
+        pub transport: InnerClient,
+        #[jsonrpc_client(base_url)]
+        pub url: ExampleDotOrg,
+    }
+}
+
 // TODO: test for attr on multiple fields
 
 #[test]
@@ -189,3 +530,333 @@ async fn creates_correct_v2_default_request() {
         r#"{"id":0,"jsonrpc":"2.0","method":"subtract","params":{"minuend":4,"subtrahend":5}}"#
     );
 }
+
+#[test]
+fn creates_correct_blocking_request() {
+    let client = derive_blocking_on_named_inner::Client {
+        inner: InnerClient::with_next_response(Response::new_v2_result(Id::Number(1), 1)),
+        ..derive_blocking_on_named_inner::Client::default()
+    };
+
+    let result = CalculatorBlocking::subtract(&client, 5, 4).unwrap();
+
+    assert_eq!(result, 1);
+    assert_eq!(
+        client.inner.take_recorded_request(),
+        r#"{"id":0,"jsonrpc":"2.0","method":"subtract","params":{"minuend":4,"subtrahend":5}}"#
+    );
+}
+
+#[tokio::test]
+async fn creates_correct_local_request() {
+    let client = derive_blocking_on_named_inner::Client {
+        inner: InnerClient::with_next_response(Response::new_v2_result(Id::Number(1), 1)),
+        ..derive_blocking_on_named_inner::Client::default()
+    };
+
+    let result = CalculatorLocal::subtract(&client, 5, 4).await.unwrap();
+
+    assert_eq!(result, 1);
+    assert_eq!(
+        client.inner.take_recorded_request(),
+        r#"{"id":0,"jsonrpc":"2.0","method":"subtract","params":{"minuend":4,"subtrahend":5}}"#
+    );
+}
+
+#[tokio::test]
+async fn implements_stacked_traits_on_tagged_fields() {
+    let client = derive_stacked_on_tagged_fields::Client {
+        transport: InnerClient::with_next_response(Response::new_v2_result(Id::Number(1), 1)),
+        ..derive_stacked_on_tagged_fields::Client::default()
+    };
+
+    let result = CalculatorBlocking::subtract(&client, 5, 4).unwrap();
+    assert_eq!(result, 1);
+
+    client.transport.next_response.set(Some(
+        serde_json::to_string(&Response::new_v2_result(Id::Number(2), 2)).unwrap(),
+    ));
+    let result = Calculator::subtract(&client, 6, 4).await.unwrap();
+    assert_eq!(result, 2);
+}
+
+#[tokio::test]
+async fn implements_stacked_traits_with_imported_macros() {
+    let client = derive_stacked_with_imported_macros::Client {
+        transport: InnerClient::with_next_response(Response::new_v2_result(Id::Number(1), 1)),
+        ..derive_stacked_with_imported_macros::Client::default()
+    };
+
+    let result = CalculatorBlocking::subtract(&client, 5, 4).unwrap();
+    assert_eq!(result, 1);
+
+    client.transport.next_response.set(Some(
+        serde_json::to_string(&Response::new_v2_result(Id::Number(2), 2)).unwrap(),
+    ));
+    let result = Calculator::subtract(&client, 6, 4).await.unwrap();
+    assert_eq!(result, 2);
+}
+
+#[tokio::test]
+async fn parses_response_of_transport() {
+    let client = derive_on_transport::Client::default();
+
+    let result = MathV2::subtract(&client, 5, 4).await.unwrap();
+
+    assert_eq!(result, 1);
+}
+
+#[tokio::test]
+async fn surfaces_error_response_of_transport() {
+    let client = derive_on_transport::Client {
+        inner: StaticTransport(
+            r#"{"jsonrpc":"2.0","id":0,"error":{"code":-32601,"message":"Method not found"}}"#,
+        ),
+        ..derive_on_transport::Client::default()
+    };
+
+    let error = MathV2::subtract(&client, 5, 4).await.unwrap_err();
+
+    assert!(matches!(error, Error::JsonRpc(error) if error.code == -32601));
+}
+
+#[tokio::test]
+async fn native_methods_return_send_futures() {
+    fn assert_send<T: Send>(future: T) -> T {
+        future
+    }
+
+    let client = derive_on_transport::Client::default();
+    let summands = vec![1, 2];
+
+    let difference = assert_send(MathNative::subtract(&client, 5, 4))
+        .await
+        .unwrap();
+    let sum = assert_send(client.sum(&summands)).await.unwrap();
+
+    assert_eq!(difference, 1);
+    assert_eq!(sum, 1);
+}
+
+#[tokio::test]
+async fn returns_raw_and_lazy_results() {
+    let client = derive_blocks_on_transport::Client {
+        inner: StaticTransport(r#"{"jsonrpc":"2.0","id":0,"result":{"height":42,"tx":["00ff"]}}"#),
+        ..derive_blocks_on_transport::Client::default()
+    };
+
+    let block = client.getblock("00ff".to_owned()).await.unwrap();
+    let header = client.getblockheader("00ff".to_owned()).await.unwrap();
+
+    assert_eq!(block.get(), r#"{"height":42,"tx":["00ff"]}"#);
+    assert_eq!(header.get().unwrap(), BlockHeader { height: 42 });
+}
+
+#[tokio::test]
+async fn send_streaming_defaults_to_whole_response() {
+    let transport = StaticTransport::default();
+
+    let chunks = Transport::send_streaming(&transport, &ExampleDotOrg::default(), Bytes::new())
+        .await
+        .unwrap()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+
+    assert_eq!(chunks, vec![Bytes::from_static(transport.0.as_bytes())]);
+}
+
+#[tokio::test]
+async fn sends_requests_through_dyn_transport() {
+    let transports: Vec<Arc<dyn DynTransport>> = vec![
+        Arc::new(StaticTransport::default()),
+        Arc::new(InnerClient::with_next_response(Response::new_v2_result(
+            Id::Number(1),
+            1,
+        ))),
+    ];
+
+    for inner in transports {
+        let client = derive_on_dyn_transport::Client {
+            inner,
+            base_url: ExampleDotOrg::default(),
+        };
+
+        let result = MathV2::subtract(&client, 5, 4).await.unwrap();
+
+        assert_eq!(result, 1);
+    }
+}
+
+#[tokio::test]
+async fn sends_one_request_at_a_time_through_exclusive_transport() {
+    let mut client = derive_on_exclusive_transport::Client::default();
+
+    let (first, second) = futures_util::future::join(
+        MathV2::subtract(&client, 5, 4),
+        MathV2::subtract(&client, 5, 4),
+    )
+    .await;
+    let mut results = vec![first.unwrap(), second.unwrap()];
+    results.sort_unstable();
+
+    assert_eq!(results, vec![1, 2]);
+    assert_eq!(client.inner.get_mut().requests_sent, 2);
+}
+
+#[tokio::test]
+async fn implements_generic_client_for_any_transport() {
+    let base_url = ExampleDotOrg::default();
+
+    let client = derive_on_generic_client::Client {
+        inner: StaticTransport::default(),
+        base_url: &base_url,
+    };
+    let failing_client = derive_on_generic_client::Client {
+        inner: FailingTransport,
+        base_url: &base_url,
+    };
+    let blocking_client = derive_on_generic_client::Client {
+        inner: InnerClient::with_next_response(Response::new_v2_result(Id::Number(1), 1)),
+        base_url: &base_url,
+    };
+
+    let result = MathV2::subtract(&client, 5, 4).await.unwrap();
+    let error = MathV2::subtract(&failing_client, 5, 4).await.unwrap_err();
+    let blocking_result = CalculatorBlocking::subtract(&blocking_client, 5, 4).unwrap();
+
+    assert_eq!(result, 1);
+    assert!(matches!(error, Error::Client(DummyError)));
+    assert_eq!(blocking_result, 1);
+}
+
+#[tokio::test]
+async fn implements_multiple_traits_with_paths() {
+    let client = derive_multiple_traits_with_paths::Client::default();
+
+    MathV1::subtract(&client, 5, 4).await.unwrap();
+    MathV2::subtract(&client, 5, 4).await.unwrap();
+    MathNative::subtract(&client, 5, 4).await.unwrap();
+
+    assert_eq!(
+        *client.inner.targets.lock().unwrap(),
+        vec![
+            "http://example.org/v1",
+            "http://example.org/",
+            "http://example.org/native/"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn erases_errors_of_dyn_transport() {
+    let client = derive_on_dyn_transport::Client {
+        inner: Arc::new(FailingTransport),
+        base_url: ExampleDotOrg::default(),
+    };
+
+    let error = MathV2::subtract(&client, 5, 4).await.unwrap_err();
+
+    assert!(matches!(error, Error::Client(error) if error.get_ref().is::<DummyError>()));
+}
+
+#[tokio::test]
+async fn dispatches_requests_to_transport_of_variant() {
+    let static_client = derive_on_enum::Client::Static {
+        inner: StaticTransport::default(),
+        base_url: ExampleDotOrg::default(),
+    };
+    let recording_client = derive_on_enum::Client::Recording(
+        "http://example.org/".parse().unwrap(),
+        RecordingTransport::default(),
+    );
+    let failing_client = derive_on_enum::Client::Failing {
+        inner: FailingTransport,
+        base_url: ExampleDotOrg::default(),
+    };
+    let balanced_client = derive_on_enum::Client::Balanced {
+        inner: RecordingTransport::default(),
+        base_url: RoundRobin::default(),
+    };
+
+    let result = MathV2::subtract(&static_client, 5, 4).await.unwrap();
+    let recorded_result = MathV1::subtract(&recording_client, 5, 4).await.unwrap();
+    let error = MathV2::subtract(&failing_client, 5, 4).await.unwrap_err();
+    MathV2::subtract(&balanced_client, 5, 4).await.unwrap();
+    MathV2::subtract(&balanced_client, 5, 4).await.unwrap();
+
+    assert_eq!(result, 1);
+    assert_eq!(recorded_result, 1);
+    assert!(matches!(
+        balanced_client,
+        derive_on_enum::Client::Balanced { inner, .. } if *inner.targets.lock().unwrap() == vec!["http://example.org/", "http://example.com/"]
+    ));
+    assert!(matches!(
+        recording_client,
+        derive_on_enum::Client::Recording(_, inner) if *inner.targets.lock().unwrap() == vec!["http://example.org/v1"]
+    ));
+    assert!(matches!(error, Error::Client(error) if error.get_ref().is::<DummyError>()));
+}
+
+#[tokio::test]
+async fn resolves_endpoint_for_every_request() {
+    let client = derive_on_endpoint::Client::<RoundRobin>::default();
+
+    MathV2::subtract(&client, 5, 4).await.unwrap();
+    MathV2::subtract(&client, 5, 4).await.unwrap();
+    MathV1::subtract(&client, 5, 4).await.unwrap();
+
+    assert_eq!(
+        *client.inner.targets.lock().unwrap(),
+        vec![
+            "http://example.org/",
+            "http://example.com/",
+            "http://example.org/v1"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn resolves_base_url_through_method() {
+    let client = derive_on_endpoint_method::Client {
+        shard: 7,
+        ..derive_on_endpoint_method::Client::default()
+    };
+
+    MathV2::subtract(&client, 5, 4).await.unwrap();
+
+    assert_eq!(
+        *client.inner.targets.lock().unwrap(),
+        vec!["http://shard-7.example.org/"]
+    );
+}
+
+#[tokio::test]
+async fn calls_api_through_trait_object() {
+    let apis: Vec<Arc<dyn MathV2<Error = DummyError>>> = vec![
+        Arc::new(MockMath),
+        Arc::new(derive_on_transport::Client::default()),
+    ];
+
+    for api in apis {
+        let result = api.subtract(5, 4).await.unwrap();
+
+        assert_eq!(result, 1);
+    }
+}
+
+#[tokio::test]
+async fn calls_helper_methods_of_api() {
+    let client = derive_blocking_on_named_inner::Client {
+        inner: InnerClient::with_next_response(Response::new_v2_result(Id::Number(1), -5)),
+        ..derive_blocking_on_named_inner::Client::default()
+    };
+
+    let result = CalculatorLocal::negate(&client, 5).await.unwrap();
+
+    assert_eq!(result, -5);
+    assert_eq!(
+        client.inner.take_recorded_request(),
+        r#"{"id":0,"jsonrpc":"2.0","method":"subtract","params":{"minuend":5,"subtrahend":0}}"#
+    );
+}
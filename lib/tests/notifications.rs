@@ -0,0 +1,96 @@
+#![cfg(feature = "macros")]
+
+use jsonrpc_client::{DispatchError, Notification};
+use serde::Deserialize;
+use std::sync::Mutex;
+
+#[derive(Deserialize, Debug, PartialEq)]
+pub struct Diagnostics {
+    uri: String,
+    version: u64,
+}
+
+#[jsonrpc_client::notifications]
+pub trait LanguageServerNotifications {
+    #[jsonrpc_client(method = "textDocument/publishDiagnostics")]
+    fn on_publish_diagnostics(&self, params: Diagnostics);
+
+    fn on_exit(&self);
+}
+
+#[derive(Default)]
+struct Recorder {
+    diagnostics: Mutex<Vec<Diagnostics>>,
+    exited: Mutex<bool>,
+}
+
+impl LanguageServerNotifications for Recorder {
+    fn on_publish_diagnostics(&self, params: Diagnostics) {
+        self.diagnostics.lock().unwrap().push(params);
+    }
+
+    fn on_exit(&self) {
+        *self.exited.lock().unwrap() = true;
+    }
+}
+
+fn notification(json: &str) -> Notification {
+    serde_json::from_str(json).unwrap()
+}
+
+#[test]
+fn dispatches_notification_with_custom_method_name() {
+    let recorder = Recorder::default();
+
+    recorder
+        .dispatch_notification(notification(
+            r#"{"jsonrpc":"2.0","method":"textDocument/publishDiagnostics","params":{"uri":"file:///main.rs","version":3}}"#,
+        ))
+        .unwrap();
+
+    assert_eq!(
+        *recorder.diagnostics.lock().unwrap(),
+        vec![Diagnostics {
+            uri: "file:///main.rs".to_owned(),
+            version: 3
+        }]
+    );
+}
+
+#[test]
+fn dispatches_notification_without_params_to_method_without_prefix() {
+    let recorder = Recorder::default();
+
+    recorder
+        .dispatch_notification(notification(r#"{"jsonrpc":"2.0","method":"exit"}"#))
+        .unwrap();
+
+    assert!(*recorder.exited.lock().unwrap());
+}
+
+#[test]
+fn reports_unknown_method() {
+    let recorder = Recorder::default();
+
+    let error = recorder
+        .dispatch_notification(notification(r#"{"jsonrpc":"2.0","method":"$/progress"}"#))
+        .unwrap_err();
+
+    assert!(matches!(error, DispatchError::UnknownMethod(method) if method == "$/progress"));
+}
+
+#[test]
+fn reports_invalid_params() {
+    let recorder = Recorder::default();
+
+    let error = recorder
+        .dispatch_notification(notification(
+            r#"{"jsonrpc":"2.0","method":"textDocument/publishDiagnostics","params":{"uri":"file:///main.rs"}}"#,
+        ))
+        .unwrap_err();
+
+    assert!(
+        matches!(error, DispatchError::InvalidParams { method, .. } if method == "textDocument/publishDiagnostics")
+    );
+    assert!(recorder.diagnostics.lock().unwrap().is_empty());
+}
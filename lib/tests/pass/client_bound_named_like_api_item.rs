@@ -0,0 +1,31 @@
+use jsonrpc_client::{Transport, Url};
+
+mod api {
+    #[jsonrpc_client::api]
+    pub trait Math {
+        async fn subtract(&self, subtrahend: i64, minuend: i64) -> i64;
+    }
+
+    pub trait Marker {}
+}
+
+pub trait Marker {}
+
+#[jsonrpc_client::implement(api::Math)]
+pub struct Client<T>
+where
+    T: Transport + Marker,
+{
+    inner: T,
+    base_url: Url,
+}
+
+#[allow(dead_code)]
+async fn difference<T>(client: &Client<T>) -> i64
+where
+    T: Transport + Marker + Sync,
+{
+    api::Math::subtract(client, 5, 4).await.unwrap()
+}
+
+fn main() {}
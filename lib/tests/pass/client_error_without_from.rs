@@ -7,7 +7,7 @@ pub trait Math {
     async fn subtract(&self, subtrahend: i64, minuend: i64) -> i64;
 }
 
-struct InnerClient;
+pub struct InnerClient;
 
 #[derive(Debug)]
 pub struct DummyError;
@@ -24,7 +24,7 @@ impl std::error::Error for DummyError {}
 impl SendRequest for InnerClient {
     type Error = DummyError;
 
-    async fn send_request<P>(&self, endpoint: Url, body: String) -> Result<Response<P>, Self::Error>
+    async fn send_request<P>(&self, _: Url, _: String) -> Result<Response<P>, Self::Error>
     where
         P: DeserializeOwned,
     {
@@ -38,4 +38,15 @@ pub struct Client {
     base_url: Url,
 }
 
-fn main() {}
+async fn difference(client: &impl Math) -> i64 {
+    client.subtract(5, 4).await.unwrap()
+}
+
+fn main() {
+    let client = Client {
+        inner: InnerClient,
+        base_url: "http://example.org".parse().unwrap(),
+    };
+
+    let _ = difference(&client);
+}
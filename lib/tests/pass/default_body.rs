@@ -0,0 +1,40 @@
+use jsonrpc_client::Error;
+
+#[jsonrpc_client::api(blocking, local)]
+pub trait Wallet {
+    async fn getbalance(&self) -> u64;
+
+    async fn balance_in_btc(&self) -> Result<f64, Error<Self::Error>> {
+        Ok(self.getbalance().await? as f64 / 1e8)
+    }
+
+    fn satoshis_per_btc(&self) -> u64 {
+        100_000_000
+    }
+}
+
+mod native {
+    use jsonrpc_client::Error;
+
+    #[jsonrpc_client::api(native)]
+    pub trait Wallet {
+        async fn getbalance(&self) -> u64;
+
+        async fn balance_in_btc(&self) -> Result<f64, Error<Self::Error>> {
+            Ok(self.getbalance().await? as f64 / 1e8)
+        }
+
+        fn satoshis_per_btc(&self) -> u64 {
+            100_000_000
+        }
+    }
+
+    fn assert_send<T: Send>(_: T) {}
+
+    #[allow(dead_code)]
+    fn balance_in_btc_is_send(wallet: &impl Wallet) {
+        assert_send(wallet.balance_in_btc());
+    }
+}
+
+fn main() {}
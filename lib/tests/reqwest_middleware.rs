@@ -0,0 +1,149 @@
+#![cfg(all(feature = "macros", feature = "reqwest-middleware"))]
+
+use hyper::{
+    header::AUTHORIZATION,
+    service::{make_service_fn, service_fn},
+    Body, Request, Server,
+};
+use jsonrpc_client::Url;
+use reqwest_middleware::{ClientBuilder, ClientWithMiddleware, Middleware, Next};
+use serde_json::{json, Value};
+use std::{convert::Infallible, net::SocketAddr};
+use task_local_extensions::Extensions;
+
+#[jsonrpc_client::api]
+pub trait Math {
+    async fn subtract(&self, subtrahend: i64, minuend: i64) -> i64;
+}
+
+#[jsonrpc_client::implement(Math)]
+struct Client {
+    inner: ClientWithMiddleware,
+    base_url: Url,
+}
+
+/// Adds a bearer token to every request.
+struct BearerAuth;
+
+#[async_trait::async_trait]
+impl Middleware for BearerAuth {
+    async fn handle(
+        &self,
+        mut request: reqwest::Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<reqwest::Response> {
+        request
+            .headers_mut()
+            .insert(AUTHORIZATION, "Bearer secret".parse().unwrap());
+
+        next.run(request, extensions).await
+    }
+}
+
+#[derive(Debug)]
+struct Unavailable;
+
+impl std::fmt::Display for Unavailable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("circuit is open")
+    }
+}
+
+impl std::error::Error for Unavailable {}
+
+/// Fails every request without sending it.
+struct CircuitBreaker;
+
+#[async_trait::async_trait]
+impl Middleware for CircuitBreaker {
+    async fn handle(
+        &self,
+        _: reqwest::Request,
+        _: &mut Extensions,
+        _: Next<'_>,
+    ) -> reqwest_middleware::Result<reqwest::Response> {
+        Err(reqwest_middleware::Error::middleware(Unavailable))
+    }
+}
+
+async fn subtract(request: Request<Body>) -> Result<hyper::Response<Body>, Infallible> {
+    let authorization = request.headers().get(AUTHORIZATION).cloned();
+    let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+    let request = serde_json::from_slice::<Value>(&body).unwrap();
+    let params = &request["params"];
+
+    let response = match authorization {
+        Some(authorization) if authorization == "Bearer secret" => json!({
+            "jsonrpc": "2.0",
+            "id": request["id"],
+            "result": params["subtrahend"].as_i64().unwrap() - params["minuend"].as_i64().unwrap()
+        }),
+        _ => json!({
+            "jsonrpc": "2.0",
+            "id": request["id"],
+            "error": { "code": -32600, "message": "unauthorized" }
+        }),
+    };
+
+    Ok(hyper::Response::new(Body::from(response.to_string())))
+}
+
+fn start_server() -> SocketAddr {
+    let make_service = make_service_fn(|_| async { Ok::<_, Infallible>(service_fn(subtract)) });
+    let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+    let address = server.local_addr();
+
+    tokio::spawn(server);
+
+    address
+}
+
+#[tokio::test]
+async fn sends_requests_through_middleware() {
+    let address = start_server();
+    let client = Client {
+        inner: ClientBuilder::new(reqwest::Client::new())
+            .with(BearerAuth)
+            .build(),
+        base_url: format!("http://{}/", address).parse().unwrap(),
+    };
+
+    let result = client.subtract(10, 5).await.unwrap();
+
+    assert_eq!(result, 5);
+}
+
+#[tokio::test]
+async fn preserves_middleware_errors() {
+    let address = start_server();
+    let client = Client {
+        inner: ClientBuilder::new(reqwest::Client::new())
+            .with(CircuitBreaker)
+            .build(),
+        base_url: format!("http://{}/", address).parse().unwrap(),
+    };
+
+    let error = client.subtract(10, 5).await.unwrap_err();
+
+    assert!(matches!(
+        error,
+        jsonrpc_client::Error::Client(reqwest_middleware::Error::Middleware(e)) if e.is::<Unavailable>()
+    ));
+}
+
+#[tokio::test]
+async fn preserves_reqwest_errors() {
+    let client = Client {
+        inner: ClientBuilder::new(reqwest::Client::new()).build(),
+        // nothing listens on the discard port
+        base_url: "http://127.0.0.1:9/".parse().unwrap(),
+    };
+
+    let error = client.subtract(10, 5).await.unwrap_err();
+
+    assert!(matches!(
+        error,
+        jsonrpc_client::Error::Client(reqwest_middleware::Error::Reqwest(e)) if e.is_connect()
+    ));
+}
@@ -0,0 +1,116 @@
+#![cfg(all(feature = "macros", feature = "stdio"))]
+
+use bytes::Bytes;
+use futures_util::{SinkExt, StreamExt};
+use jsonrpc_client::{codec::ContentLengthCodec, stdio, stream::StreamClient, JsonRpcError, Url};
+use serde_json::{json, Value};
+use std::time::Duration;
+use tokio_util::codec::Framed;
+
+#[jsonrpc_client::api]
+pub trait Math {
+    async fn subtract(&self, subtrahend: i64, minuend: i64) -> i64;
+}
+
+#[jsonrpc_client::implement(Math)]
+struct Client {
+    inner: StreamClient,
+    base_url: Url,
+}
+
+#[tokio::test]
+async fn sends_content_length_framed_requests() {
+    let (client_io, server_io) = tokio::io::duplex(1024);
+    let (reader, writer) = tokio::io::split(client_io);
+
+    tokio::spawn(async move {
+        let mut server = Framed::new(server_io, ContentLengthCodec::default());
+
+        let request =
+            serde_json::from_slice::<Value>(&server.next().await.unwrap().unwrap()).unwrap();
+        let response = json!({
+            "jsonrpc": "2.0",
+            "id": request["id"],
+            "result": request["params"]["subtrahend"].as_i64().unwrap() - request["params"]["minuend"].as_i64().unwrap()
+        });
+
+        server
+            .send(Bytes::from(serde_json::to_vec(&response).unwrap()))
+            .await
+            .unwrap();
+    });
+
+    let client = Client {
+        inner: stdio::connect(reader, writer),
+        base_url: "stdio:".parse().unwrap(),
+    };
+
+    let result = client.subtract(10, 5).await.unwrap();
+
+    assert_eq!(result, 5);
+}
+
+#[tokio::test]
+async fn answers_requests_sent_by_the_server() {
+    let (client_io, server_io) = tokio::io::duplex(1024);
+    let (reader, writer) = tokio::io::split(client_io);
+    let client = stdio::connect(reader, writer);
+
+    client.handle("workspace/configuration", |params: Value| async move {
+        Ok::<_, JsonRpcError>(vec![params["items"][0]["section"].clone()])
+    });
+
+    let mut server = Framed::new(server_io, ContentLengthCodec::default());
+    for request in &[
+        json!({ "jsonrpc": "2.0", "id": 1, "method": "workspace/configuration", "params": { "items": [{ "section": "rust" }] } }),
+        json!({ "jsonrpc": "2.0", "id": "2", "method": "window/showDocument", "params": {} }),
+    ] {
+        server
+            .send(Bytes::from(serde_json::to_vec(request).unwrap()))
+            .await
+            .unwrap();
+    }
+
+    let mut responses = Vec::new();
+    for _ in 0..2 {
+        let response =
+            serde_json::from_slice::<Value>(&server.next().await.unwrap().unwrap()).unwrap();
+        responses.push(response);
+    }
+    responses.sort_by_key(|response| response["id"].to_string());
+
+    assert_eq!(
+        responses,
+        vec![
+            json!({ "jsonrpc": "2.0", "id": "2", "error": { "code": -32601, "message": "method `window/showDocument` not found", "data": null } }),
+            json!({ "jsonrpc": "2.0", "id": 1, "result": ["rust"] }),
+        ]
+    );
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn shutdown_closes_stdin_of_child() {
+    let child = stdio::ChildProcess::spawn(
+        tokio::process::Command::new("sh")
+            .arg("-c")
+            .arg("cat > /dev/null"),
+    )
+    .unwrap();
+
+    let status = child.shutdown(Duration::from_secs(10)).await.unwrap();
+
+    assert!(status.success());
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn shutdown_kills_child_after_grace_period() {
+    let child =
+        stdio::ChildProcess::spawn(tokio::process::Command::new("sh").arg("-c").arg("sleep 30"))
+            .unwrap();
+
+    let status = child.shutdown(Duration::from_millis(100)).await.unwrap();
+
+    assert!(!status.success());
+}
@@ -0,0 +1,109 @@
+#![cfg(all(feature = "streaming", feature = "hyper"))]
+
+use bytes::Bytes;
+use hyper::{
+    service::{make_service_fn, service_fn},
+    Body, Request, Server,
+};
+use jsonrpc_client::{streaming, Error, RequestWriter, Url};
+use serde::Deserialize;
+use std::{convert::Infallible, io, net::SocketAddr};
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Block {
+    height: u64,
+    tx: Vec<String>,
+}
+
+const TRANSACTIONS: usize = 1000;
+
+/// Answers every request with a block of many transactions, sent in one chunk per transaction.
+///
+/// The `truncated` method aborts the response halfway through.
+async fn getblock(request: Request<Body>) -> Result<hyper::Response<Body>, Infallible> {
+    let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+    let truncated =
+        serde_json::from_slice::<serde_json::Value>(&body).unwrap()["method"] == "truncated";
+
+    let transactions = (0..TRANSACTIONS).map(|i| {
+        let separator = if i == 0 { "" } else { "," };
+
+        Ok(Bytes::from(format!(r#"{}"{:064x}""#, separator, i)))
+    });
+    let chunks = std::iter::once(Ok(Bytes::from_static(
+        br#"{"jsonrpc":"2.0","id":0,"result":{"height":42,"tx":["#,
+    )))
+    .chain(transactions)
+    .chain(std::iter::once(Ok(Bytes::from_static(b"]}}"))));
+
+    let body = if truncated {
+        Body::wrap_stream(futures_util::stream::iter(
+            chunks
+                .take(TRANSACTIONS / 2)
+                .chain(std::iter::once(Err(io::Error::other("aborted")))),
+        ))
+    } else {
+        Body::wrap_stream(futures_util::stream::iter(chunks))
+    };
+
+    Ok(hyper::Response::new(body))
+}
+
+fn start_server() -> Url {
+    let make_service = make_service_fn(|_| async { Ok::<_, Infallible>(service_fn(getblock)) });
+    let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+    let address: SocketAddr = server.local_addr();
+
+    tokio::spawn(server);
+
+    format!("http://{}/", address).parse().unwrap()
+}
+
+fn request(method: &str) -> Bytes {
+    RequestWriter::new_v2(method).finish()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn deserializes_chunked_response() {
+    let url = start_server();
+
+    let block = streaming::send::<_, Block>(
+        &hyper::Client::new(),
+        &url,
+        request("getblock"),
+        streaming::DEFAULT_MAX_RESPONSE_SIZE,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(block.height, 42);
+    assert_eq!(block.tx.len(), TRANSACTIONS);
+    assert_eq!(block.tx[1], format!("{:064x}", 1));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn enforces_max_response_size() {
+    let url = start_server();
+
+    let error = streaming::send::<_, Block>(&hyper::Client::new(), &url, request("getblock"), 1024)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, Error::ResponseTooLarge(1024)));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn surfaces_errors_of_transport_while_receiving() {
+    let url = start_server();
+
+    let error = streaming::send::<_, Block>(
+        &hyper::Client::new(),
+        &url,
+        request("truncated"),
+        streaming::DEFAULT_MAX_RESPONSE_SIZE,
+    )
+    .await
+    .unwrap_err();
+
+    assert!(matches!(error, Error::Client(_)), "{:?}", error);
+}
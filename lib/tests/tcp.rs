@@ -0,0 +1,149 @@
+#![cfg(all(feature = "macros", feature = "tcp"))]
+
+use jsonrpc_client::{stream::StreamClient, tcp, Error, Url};
+use serde_json::{json, Value};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::{tcp::OwnedWriteHalf, TcpListener},
+};
+
+#[jsonrpc_client::api]
+pub trait Math {
+    async fn subtract(&self, subtrahend: i64, minuend: i64) -> i64;
+}
+
+#[jsonrpc_client::implement(Math)]
+struct Client {
+    inner: StreamClient,
+    base_url: Url,
+}
+
+async fn listen() -> (TcpListener, Url) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("tcp://{}", listener.local_addr().unwrap())
+        .parse()
+        .unwrap();
+
+    (listener, url)
+}
+
+async fn write_line(writer: &mut OwnedWriteHalf, message: Value) {
+    let mut line = serde_json::to_vec(&message).unwrap();
+    line.push(b'\n');
+
+    writer.write_all(&line).await.unwrap();
+}
+
+fn subtract(request: &Value) -> Value {
+    let params = &request["params"];
+
+    json!({
+        "jsonrpc": "2.0",
+        "id": request["id"],
+        "result": params["subtrahend"].as_i64().unwrap() - params["minuend"].as_i64().unwrap()
+    })
+}
+
+#[tokio::test]
+async fn concurrent_requests_are_matched_to_their_responses() {
+    let (listener, url) = listen().await;
+
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+
+        let first =
+            serde_json::from_str::<Value>(&lines.next_line().await.unwrap().unwrap()).unwrap();
+        let second =
+            serde_json::from_str::<Value>(&lines.next_line().await.unwrap().unwrap()).unwrap();
+
+        // respond in reverse order
+        write_line(&mut writer, subtract(&second)).await;
+        write_line(&mut writer, subtract(&first)).await;
+    });
+
+    let client = Client {
+        inner: tcp::connect(&url).await.unwrap(),
+        base_url: url,
+    };
+
+    let (first, second) = tokio::join!(client.subtract(10, 5), client.subtract(3, 1));
+
+    assert_eq!(first.unwrap(), 5);
+    assert_eq!(second.unwrap(), 2);
+}
+
+#[tokio::test]
+async fn forwards_server_notifications_to_subscribers() {
+    let (listener, url) = listen().await;
+
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+
+        let request =
+            serde_json::from_str::<Value>(&lines.next_line().await.unwrap().unwrap()).unwrap();
+
+        write_line(
+            &mut writer,
+            json!({
+                "jsonrpc": "2.0",
+                "method": "blockchain.headers.subscribe",
+                "params": [{ "height": 520481 }]
+            }),
+        )
+        .await;
+        write_line(&mut writer, subtract(&request)).await;
+    });
+
+    let client = Client {
+        inner: tcp::connect(&url).await.unwrap(),
+        base_url: url,
+    };
+    let mut notifications = client.inner.notifications();
+
+    let result = client.subtract(10, 5).await.unwrap();
+    let notification = notifications.recv().await.unwrap();
+
+    assert_eq!(result, 5);
+    assert_eq!(notification.method, "blockchain.headers.subscribe");
+    assert_eq!(notification.params, Some(json!([{ "height": 520481 }])));
+}
+
+#[tokio::test]
+async fn pending_requests_fail_when_connection_is_closed() {
+    let (listener, url) = listen().await;
+
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut lines = BufReader::new(stream).lines();
+
+        lines.next_line().await.unwrap().unwrap();
+    });
+
+    let client = Client {
+        inner: tcp::connect(&url).await.unwrap(),
+        base_url: url,
+    };
+
+    let error = client.subtract(10, 5).await.unwrap_err();
+
+    assert!(matches!(
+        error,
+        Error::Client(jsonrpc_client::stream::Error::ConnectionClosed)
+    ));
+}
+
+#[tokio::test]
+async fn rejects_urls_without_tcp_scheme() {
+    let url = "http://127.0.0.1:50001".parse().unwrap();
+
+    let error = tcp::connect(&url).await.unwrap_err();
+
+    assert!(matches!(
+        error,
+        jsonrpc_client::stream::Error::UnsupportedUrl(_)
+    ));
+}
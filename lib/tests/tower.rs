@@ -0,0 +1,99 @@
+#![cfg(all(feature = "macros", feature = "tower"))]
+
+use bytes::Bytes;
+use jsonrpc_client::{
+    tower::{BoxError, JsonRpcService, ServiceTransport},
+    Request, Url,
+};
+use serde_json::{json, Value};
+use std::{convert::Infallible, time::Duration};
+use tower::{
+    service_fn, timeout::error::Elapsed, util::BoxCloneService, ServiceBuilder, ServiceExt,
+};
+
+type HttpService = BoxCloneService<http::Request<Bytes>, http::Response<Bytes>, BoxError>;
+
+#[jsonrpc_client::api]
+pub trait Math {
+    async fn subtract(&self, subtrahend: i64, minuend: i64) -> i64;
+}
+
+#[jsonrpc_client::implement(Math)]
+struct Client {
+    inner: ServiceTransport<HttpService>,
+    base_url: Url,
+}
+
+/// Answers `subtract` requests after the given delay, with a timeout of 100 milliseconds.
+fn http_service(delay: Duration) -> HttpService {
+    let service = ServiceBuilder::new()
+        .timeout(Duration::from_millis(100))
+        .service(service_fn(move |request: http::Request<Bytes>| async move {
+            tokio::time::sleep(delay).await;
+
+            let request = serde_json::from_slice::<Value>(request.body()).unwrap();
+            let params = &request["params"];
+            let response = json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "result": params["subtrahend"].as_i64().unwrap() - params["minuend"].as_i64().unwrap()
+            });
+
+            Ok::<_, Infallible>(http::Response::new(Bytes::from(response.to_string())))
+        }));
+
+    BoxCloneService::new(service)
+}
+
+fn client(delay: Duration) -> Client {
+    Client {
+        inner: ServiceTransport::new(http_service(delay)),
+        base_url: "http://example.org/".parse().unwrap(),
+    }
+}
+
+#[tokio::test]
+async fn sends_requests_through_service() {
+    let client = client(Duration::ZERO);
+
+    let result = client.subtract(10, 5).await.unwrap();
+
+    assert_eq!(result, 5);
+}
+
+#[tokio::test]
+async fn surfaces_errors_of_layers() {
+    let client = client(Duration::from_secs(10));
+
+    let error = client.subtract(10, 5).await.unwrap_err();
+
+    assert!(matches!(
+        error,
+        jsonrpc_client::Error::Client(jsonrpc_client::tower::Error::Service(e)) if e.is::<Elapsed>()
+    ));
+}
+
+#[tokio::test]
+async fn layers_wrap_transports() {
+    let service = JsonRpcService::new(
+        ServiceTransport::new(http_service(Duration::ZERO)),
+        "http://example.org/".parse().unwrap(),
+    );
+    let request = Request::new_v2("subtract")
+        .with_argument("subtrahend".to_owned(), 10)
+        .unwrap()
+        .with_argument("minuend".to_owned(), 5)
+        .unwrap();
+
+    let result = ServiceBuilder::new()
+        .map_response(|response: jsonrpc_client::Response<Value>| {
+            Result::<Value, _>::from(response.payload)
+        })
+        .service(service)
+        .oneshot(request)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(result, json!(5));
+}
@@ -1,8 +0,0 @@
-#[jsonrpc_client::api]
-pub trait Math {
-    async fn subtract(&self, subtrahend: i64, minuend: i64) -> i64 {
-        10
-    }
-}
-
-fn main() {}
@@ -0,0 +1,18 @@
+use jsonrpc_client::{DynTransport, Url};
+use std::sync::Arc;
+
+#[jsonrpc_client::api]
+pub trait Math {
+    async fn subtract(&self, subtrahend: i64, minuend: i64) -> i64;
+}
+
+#[jsonrpc_client::implement(Math)]
+pub enum Client {
+    Dyn {
+        inner: Arc<dyn DynTransport>,
+        base_url: Url,
+    },
+    Other(#[jsonrpc_client(inner)] Arc<dyn DynTransport>),
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+#[jsonrpc_client::api]
+pub trait Math {
+    async fn subtract(&self, subtrahend: i64, minuend: i64) -> i64;
+}
+
+#[jsonrpc_client::implement(Math(base_url = "foo"))]
+pub struct Client {
+    inner: std::sync::Arc<dyn jsonrpc_client::DynTransport>,
+    base_url: jsonrpc_client::Url,
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+#[jsonrpc_client::api]
+pub trait Math {
+    async fn subtract(&self, subtrahend: i64, minuend: i64) -> i64;
+}
+
+#[jsonrpc_client::implement(Math, std::fmt::Display)]
+pub struct Client {
+    inner: std::sync::Arc<dyn jsonrpc_client::DynTransport>,
+    base_url: jsonrpc_client::Url,
+}
+
+#[jsonrpc_client::implement(Math, Clone)]
+pub struct OtherClient {
+    inner: std::sync::Arc<dyn jsonrpc_client::DynTransport>,
+    base_url: jsonrpc_client::Url,
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+#[jsonrpc_client::notifications]
+pub trait Notifications {
+    async fn on_progress(&self, params: String);
+}
+
+fn main() {}
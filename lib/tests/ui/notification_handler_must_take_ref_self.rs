@@ -0,0 +1,6 @@
+#[jsonrpc_client::notifications]
+pub trait Notifications {
+    fn on_progress(&mut self, value: u64);
+}
+
+fn main() {}
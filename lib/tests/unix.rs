@@ -0,0 +1,125 @@
+#![cfg(all(feature = "macros", feature = "unix", unix))]
+
+use jsonrpc_client::{stream::StreamClient, unix, Url};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::UnixListener,
+};
+
+#[jsonrpc_client::api]
+pub trait LightningRpc {
+    async fn getinfo(&self) -> Value;
+}
+
+#[jsonrpc_client::implement(LightningRpc)]
+struct Client {
+    inner: StreamClient,
+    base_url: Url,
+}
+
+fn socket_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "jsonrpc-client-{}-{} rpc",
+        name,
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+
+    path
+}
+
+#[tokio::test]
+async fn decodes_concatenated_responses() {
+    let path = socket_path("concatenated");
+    let listener = UnixListener::bind(&path).unwrap();
+
+    tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut ids = Vec::new();
+        let mut buffer = Vec::new();
+
+        // requests are not delimited either, hence we wait until both have arrived
+        while ids.len() < 2 {
+            let mut chunk = [0u8; 1024];
+            let read = stream.read(&mut chunk).await.unwrap();
+            buffer.extend_from_slice(&chunk[..read]);
+
+            ids = serde_json::Deserializer::from_slice(&buffer)
+                .into_iter::<Value>()
+                .filter_map(Result::ok)
+                .map(|request| request["id"].clone())
+                .collect();
+        }
+
+        let mut responses = Vec::new();
+        for id in ids {
+            serde_json::to_writer(
+                &mut responses,
+                &json!({ "jsonrpc": "2.0", "id": id, "result": { "alias": "{\"}[" } }),
+            )
+            .unwrap();
+        }
+
+        // write the responses in tiny chunks to exercise the incremental decoding
+        for chunk in responses.chunks(7) {
+            stream.write_all(chunk).await.unwrap();
+            stream.flush().await.unwrap();
+        }
+    });
+
+    let url = Url::parse(&format!("unix://{}", path.display())).unwrap();
+    let client = Client {
+        inner: unix::connect(&url).await.unwrap(),
+        base_url: url,
+    };
+
+    let (first, second) = tokio::join!(client.getinfo(), client.getinfo());
+
+    assert_eq!(first.unwrap(), json!({ "alias": "{\"}[" }));
+    assert_eq!(second.unwrap(), json!({ "alias": "{\"}[" }));
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[tokio::test]
+async fn rejects_urls_with_host() {
+    let url = "unix://localhost/tmp/lightning-rpc".parse().unwrap();
+
+    let error = unix::connect(&url).await.unwrap_err();
+
+    assert!(matches!(
+        error,
+        jsonrpc_client::stream::Error::UnsupportedUrl(_)
+    ));
+}
+
+#[tokio::test]
+async fn connects_to_path_with_reserved_characters() {
+    let path = socket_path("reserved#?%25");
+    let listener = UnixListener::bind(&path).unwrap();
+
+    tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut buffer = vec![0u8; 1024];
+        let read = stream.read(&mut buffer).await.unwrap();
+        let request = serde_json::from_slice::<Value>(&buffer[..read]).unwrap();
+
+        let response = json!({ "jsonrpc": "2.0", "id": request["id"], "result": {} });
+        stream
+            .write_all(&serde_json::to_vec(&response).unwrap())
+            .await
+            .unwrap();
+    });
+
+    let url = unix::url_from_path(&path).unwrap();
+    let client = Client {
+        inner: unix::connect(&url).await.unwrap(),
+        base_url: url,
+    };
+
+    assert_eq!(client.getinfo().await.unwrap(), json!({}));
+
+    std::fs::remove_file(path).unwrap();
+}
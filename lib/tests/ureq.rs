@@ -0,0 +1,105 @@
+#![cfg(all(feature = "macros", feature = "ureq"))]
+
+use hyper::{
+    service::{make_service_fn, service_fn},
+    Body, Request, Server, StatusCode,
+};
+use jsonrpc_client::Url;
+use serde_json::{json, Value};
+use std::{convert::Infallible, net::SocketAddr};
+
+#[jsonrpc_client::api(blocking)]
+pub trait Math {
+    async fn subtract(&self, subtrahend: i64, minuend: i64) -> i64;
+}
+
+#[jsonrpc_client::implement_blocking(MathBlocking)]
+struct Client {
+    inner: ureq::Agent,
+    base_url: Url,
+}
+
+async fn subtract(request: Request<Body>) -> Result<hyper::Response<Body>, Infallible> {
+    let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+    let request = serde_json::from_slice::<Value>(&body).unwrap();
+    let params = &request["params"];
+
+    let subtrahend = params["subtrahend"].as_i64().unwrap();
+    let minuend = params["minuend"].as_i64().unwrap();
+
+    // answer with an HTTP error status like many JSON-RPC servers do
+    let (status, response) = match subtrahend.checked_sub(minuend) {
+        Some(difference) if difference >= 0 => (
+            StatusCode::OK,
+            json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "result": difference
+            }),
+        ),
+        _ => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "error": { "code": -32602, "message": "result must not be negative" }
+            }),
+        ),
+    };
+
+    let mut response = hyper::Response::new(Body::from(response.to_string()));
+    *response.status_mut() = status;
+
+    Ok(response)
+}
+
+fn start_server() -> SocketAddr {
+    let make_service = make_service_fn(|_| async { Ok::<_, Infallible>(service_fn(subtract)) });
+    let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+    let address = server.local_addr();
+
+    tokio::spawn(server);
+
+    address
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn sends_blocking_requests() {
+    let address = start_server();
+
+    let result = tokio::task::spawn_blocking(move || {
+        let client = Client {
+            inner: ureq::Agent::new(),
+            base_url: format!("http://{}/", address).parse().unwrap(),
+        };
+
+        client.subtract(10, 5)
+    })
+    .await
+    .unwrap()
+    .unwrap();
+
+    assert_eq!(result, 5);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn parses_json_rpc_error_from_error_status() {
+    let address = start_server();
+
+    let error = tokio::task::spawn_blocking(move || {
+        let client = Client {
+            inner: ureq::Agent::new(),
+            base_url: format!("http://{}/", address).parse().unwrap(),
+        };
+
+        client.subtract(5, 10)
+    })
+    .await
+    .unwrap()
+    .unwrap_err();
+
+    assert!(matches!(
+        error,
+        jsonrpc_client::Error::JsonRpc(jsonrpc_client::JsonRpcError { code: -32602, .. })
+    ));
+}
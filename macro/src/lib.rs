@@ -1,8 +1,14 @@
 use proc_macro::TokenStream;
-use quote::{quote, quote_spanned};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use std::collections::HashSet;
 use syn::{
-    spanned::Spanned, Error, Field, Fields, FnArg, ItemStruct, ItemTrait, Lit, Meta, MetaNameValue,
-    NestedMeta, Pat, Path, ReturnType, TraitItem, TraitItemMethod,
+    parse::{Nothing, Parser},
+    parse_quote,
+    punctuated::Punctuated,
+    spanned::Spanned,
+    Attribute, Error, ExprPath, Field, Fields, FnArg, Generics, Ident, Item, ItemEnum, ItemStruct,
+    ItemTrait, Lit, LitStr, Meta, MetaNameValue, NestedMeta, Pat, Path, Receiver, ReturnType,
+    Token, TraitItem, TraitItemMethod, Type, Visibility,
 };
 
 #[proc_macro_attribute]
@@ -15,7 +21,31 @@ pub fn api(attr: TokenStream, item: TokenStream) -> TokenStream {
 
 #[proc_macro_attribute]
 pub fn implement(attr: TokenStream, item: TokenStream) -> TokenStream {
-    match make_api_impl(item, attr) {
+    match make_api_impl(item, attr, Mode::AsyncTrait) {
+        Ok(output) => output,
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+#[proc_macro_attribute]
+pub fn implement_blocking(attr: TokenStream, item: TokenStream) -> TokenStream {
+    match make_api_impl(item, attr, Mode::Blocking) {
+        Ok(output) => output,
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+#[proc_macro_attribute]
+pub fn implement_local(attr: TokenStream, item: TokenStream) -> TokenStream {
+    match make_api_impl(item, attr, Mode::Local) {
+        Ok(output) => output,
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+#[proc_macro_attribute]
+pub fn notifications(attr: TokenStream, item: TokenStream) -> TokenStream {
+    match make_notification_dispatcher(item, attr) {
         Ok(output) => output,
         Err(e) => e.to_compile_error().into(),
     }
@@ -26,31 +56,61 @@ enum Version {
     Two,
 }
 
+/// How the methods of an API trait are generated.
+#[derive(Clone, Copy)]
+enum Mode {
+    /// `async fn`s, boxed through `async_trait`.
+    AsyncTrait,
+    /// Methods returning `impl Future + Send`.
+    Native,
+    /// Synchronous methods.
+    Blocking,
+    /// `async fn`s, boxed through `async_trait` without requiring `Send`.
+    Local,
+}
+
 fn make_new_trait(input: TokenStream, attr: TokenStream) -> Result<TokenStream, Error> {
     let trait_def = syn::parse::<ItemTrait>(input)?;
-    let version = if attr.is_empty() {
-        Version::Two
-    } else {
-        let meta_name_value = syn::parse::<MetaNameValue>(attr)?;
+    let attributes = Punctuated::<NestedMeta, Token![,]>::parse_terminated.parse(attr)?;
 
-        if !meta_name_value.path.is_ident("version") {
-            return Err(Error::new(
-                meta_name_value.path.span(),
-                "invalid configuration attribute, currently only `version` is supported",
-            ));
-        }
+    let mut version = Version::Two;
+    let mut blocking = false;
+    let mut native = false;
+    let mut local = false;
 
-        match meta_name_value.lit {
-            Lit::Str(str_lit) if str_lit.value() == "1.0" => Version::One,
-            Lit::Str(str_lit) if str_lit.value() == "2.0" => Version::Two,
-            _ => {
+    for attribute in attributes {
+        match attribute {
+            NestedMeta::Meta(Meta::NameValue(meta_name_value))
+                if meta_name_value.path.is_ident("version") =>
+            {
+                version = match meta_name_value.lit {
+                    Lit::Str(str_lit) if str_lit.value() == "1.0" => Version::One,
+                    Lit::Str(str_lit) if str_lit.value() == "2.0" => Version::Two,
+                    _ => {
+                        return Err(Error::new(
+                            meta_name_value.lit.span(),
+                            r#"argument to `version` must be either "1.0" or "2.0""#,
+                        ))
+                    }
+                }
+            }
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("blocking") => blocking = true,
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("native") => native = true,
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("local") => local = true,
+            NestedMeta::Meta(meta) => {
+                return Err(Error::new(
+                    meta.path().span(),
+                    "invalid configuration attribute, currently only `version`, `blocking`, `local` and `native` are supported",
+                ))
+            }
+            NestedMeta::Lit(lit) => {
                 return Err(Error::new(
-                    meta_name_value.lit.span(),
-                    r#"argument to `version` must be either "1.0" or "2.0""#,
+                    lit.span(),
+                    "invalid configuration attribute, currently only `version`, `blocking`, `local` and `native` are supported",
                 ))
             }
         }
-    };
+    }
 
     let methods: Vec<TraitItemMethod> = trait_def
         .items
@@ -61,96 +121,311 @@ fn make_new_trait(input: TokenStream, attr: TokenStream) -> Result<TokenStream,
         })
         .collect::<Result<Vec<_>, _>>()?;
 
-    let new_methods = methods.iter().map(|method| {
-        if method.default.is_some() {
-            return Err(Error::new(
-                method.default.span(),
-                "trait method must not have a default implementation",
-            ));
-        }
-
+    // methods with a default implementation are client-side helpers that are emitted as is
+    for method in methods.iter().filter(|method| method.default.is_none()) {
         if method.sig.asyncness.is_none() {
             return Err(Error::new(
                 method.sig.span(),
                 "trait methods have to be async",
             ));
         }
+    }
 
-        let arguments = method.sig.inputs
-            .iter()
-            .filter_map(|input| match input {
-                FnArg::Receiver(_) => None,
-                FnArg::Typed(arg) => match &*arg.pat {
-                    Pat::Ident(ident) => Some((&ident.ident, &arg.ty)),
-                    _ => None,
-                },
-            })
-            .collect::<Vec<_>>();
+    let mode = if native {
+        Mode::Native
+    } else {
+        Mode::AsyncTrait
+    };
 
-        let return_type = match &method.sig.output {
-            ReturnType::Default => quote! {
-               ()
-            },
-            ReturnType::Type(_, return_type) => quote! {
-                #return_type
-            },
-        };
+    let trait_ident = trait_def.ident;
+    let vis = trait_def.vis;
 
-        let serialized_arguments = arguments
-            .iter()
-            .map(|(argument, ty)| quote_spanned! { ty.span() => .with_argument(String::from(stringify!(#argument)), #argument)? })
-            .collect::<Vec<_>>();
+    let api_trait = make_api_trait(&methods, &version, mode, &vis, &trait_ident);
 
-        let new_request_fn = match version {
-            Version::One => quote! { new_v1 },
-            Version::Two => quote! { new_v2 },
-        };
-        let method_ident = &method.sig.ident;
-        let inputs = &method.sig.inputs;
+    let blocking_trait = if blocking {
+        let blocking_trait_ident = format_ident!("{}Blocking", trait_ident);
 
-        let send_request_call = match &method.sig.output {
-            ReturnType::Default => quote! {
-               self.send_request::<#return_type>(request).await?;
-            },
-            ReturnType::Type(_, return_type) => quote_spanned! { return_type.span() =>
-                self.send_request::<#return_type>(request).await?;
-            },
-        };
-        let attrs = &method.attrs;
+        make_api_trait(
+            &methods,
+            &version,
+            Mode::Blocking,
+            &vis,
+            &blocking_trait_ident,
+        )
+    } else {
+        quote! {}
+    };
+
+    let local_trait = if local {
+        let local_trait_ident = format_ident!("{}Local", trait_ident);
+
+        make_api_trait(&methods, &version, Mode::Local, &vis, &local_trait_ident)
+    } else {
+        quote! {}
+    };
 
-        Ok(quote! {
-            #(#attrs)*
-            async fn #method_ident(#inputs) -> Result<#return_type, ::jsonrpc_client::Error<<C as ::jsonrpc_client::SendRequest>::Error>> {
-                let request = ::jsonrpc_client::Request::#new_request_fn(stringify!(#method_ident))
-                    #(#serialized_arguments)*
-                    .serialize()?;
+    Ok(quote! {
+        #api_trait
 
-                let response = #send_request_call
-                let success = Result::from(response.payload)?;
+        #blocking_trait
 
-                Ok(success)
-            }
+        #local_trait
+    }
+    .into())
+}
+
+/// Generates an API trait in the given mode, together with the hidden trait that clients implement to send its requests.
+///
+/// The API trait itself doesn't expose any plumbing and is hence object-safe, unless its methods return `impl Future`.
+/// It is implemented for every implementation of the hidden trait.
+fn make_api_trait(
+    methods: &[TraitItemMethod],
+    version: &Version,
+    mode: Mode,
+    vis: &Visibility,
+    trait_ident: &Ident,
+) -> proc_macro2::TokenStream {
+    let transport_trait_ident = format_ident!("{}Transport", trait_ident);
+
+    let (declarations, implementations): (Vec<_>, Vec<_>) = methods
+        .iter()
+        .filter(|method| method.default.is_none())
+        .map(|method| make_api_method(method, version, mode, &transport_trait_ident))
+        .unzip();
+
+    // async helpers can't be called from the synchronous methods of a blocking trait
+    let helpers = methods
+        .iter()
+        .filter(|method| {
+            method.default.is_some()
+                && !(matches!(mode, Mode::Blocking) && method.sig.asyncness.is_some())
         })
-    }).collect::<Result<Vec<_>, _>>()?;
+        .map(|method| match mode {
+            Mode::Native if method.sig.asyncness.is_some() => make_native_helper(method),
+            _ => quote! { #method },
+        });
 
-    let trait_ident = trait_def.ident;
-    let vis = trait_def.vis;
+    let (async_trait, supertrait, send_request_output) = match mode {
+        Mode::AsyncTrait => (
+            quote! { #[::jsonrpc_client::export::async_trait::async_trait] },
+            quote! { : Sync },
+            quote! { ::jsonrpc_client::BoxFuture<'_, ::std::result::Result<::jsonrpc_client::export::bytes::Bytes, Self::Error>> },
+        ),
+        Mode::Native => (
+            quote! {},
+            quote! { : Sync },
+            quote! { ::jsonrpc_client::BoxFuture<'_, ::std::result::Result<::jsonrpc_client::export::bytes::Bytes, Self::Error>> },
+        ),
+        Mode::Local => (
+            quote! { #[::jsonrpc_client::export::async_trait::async_trait(?Send)] },
+            quote! {},
+            quote! { ::jsonrpc_client::LocalBoxFuture<'_, ::std::result::Result<::jsonrpc_client::export::bytes::Bytes, Self::Error>> },
+        ),
+        Mode::Blocking => (
+            quote! {},
+            quote! {},
+            quote! { ::std::result::Result<::jsonrpc_client::export::bytes::Bytes, Self::Error> },
+        ),
+    };
 
-    Ok(quote! {
-        #[::jsonrpc_client::export::async_trait::async_trait]
-        #vis trait #trait_ident<C> where C: ::jsonrpc_client::SendRequest, ::jsonrpc_client::Error<<C as jsonrpc_client::SendRequest>::Error>: From<<C as jsonrpc_client::SendRequest>::Error> {
-            #(#new_methods)*
+    quote! {
+        #[doc(hidden)]
+        #vis trait #transport_trait_ident #supertrait {
+            type Error: ::std::error::Error;
+
+            fn send_request(&self, request: ::jsonrpc_client::export::bytes::Bytes) -> #send_request_output;
+        }
+
+        #async_trait
+        #vis trait #trait_ident #supertrait {
+            type Error: ::std::error::Error;
+
+            #(#declarations)*
+
+            #(#helpers)*
+        }
+
+        #async_trait
+        impl<T> #trait_ident for T
+        where
+            T: #transport_trait_ident,
+        {
+            type Error = <T as #transport_trait_ident>::Error;
+
+            #(#implementations)*
+        }
+    }
+}
+
+/// Rewrites an async helper into a method returning `impl Future + Send`, like the other methods of a native trait.
+///
+/// An `async fn` in a trait doesn't promise a `Send` future, which would make the helper unusable from spawned tasks.
+fn make_native_helper(method: &TraitItemMethod) -> proc_macro2::TokenStream {
+    let attrs = &method.attrs;
+    let mut sig = method.sig.clone();
+    sig.asyncness = None;
+
+    let output = match &method.sig.output {
+        ReturnType::Default => quote! { () },
+        ReturnType::Type(_, return_type) => quote! { #return_type },
+    };
+    sig.output = parse_quote! { -> impl ::std::future::Future<Output = #output> + Send };
+
+    let body = &method.default;
+
+    quote! {
+        #(#attrs)*
+        #sig {
+            async move #body
+        }
+    }
+}
+
+/// Generates the declaration of a method of an API trait in the given mode and its implementation through the given transport trait.
+fn make_api_method(
+    method: &TraitItemMethod,
+    version: &Version,
+    mode: Mode,
+    transport_trait_ident: &Ident,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let mut arguments = method
+        .sig
+        .inputs
+        .iter()
+        .filter_map(|input| match input {
+            FnArg::Receiver(_) => None,
+            FnArg::Typed(arg) => match &*arg.pat {
+                Pat::Ident(ident) => Some((&ident.ident, &arg.ty)),
+                _ => None,
+            },
+        })
+        .collect::<Vec<_>>();
+
+    let return_type = match &method.sig.output {
+        ReturnType::Default => quote! {
+           ()
+        },
+        ReturnType::Type(_, return_type) => quote! {
+            #return_type
+        },
+    };
 
-            async fn send_request<P: ::jsonrpc_client::export::serde::de::DeserializeOwned>(&self, request: String) -> std::result::Result<::jsonrpc_client::Response<P>, <C as ::jsonrpc_client::SendRequest>::Error>;
+    // by-name params are serialized in lexicographical order, just like a `serde_json::Map`
+    if let Version::Two = version {
+        arguments.sort_by_key(|(argument, _)| argument.to_string());
+    }
+
+    let serialized_arguments = arguments
+        .iter()
+        .map(|(argument, ty)| quote_spanned! { ty.span() => .with_argument(stringify!(#argument), &#argument)? })
+        .collect::<Vec<_>>();
+
+    let new_request_fn = match version {
+        Version::One => quote! { new_v1 },
+        Version::Two => quote! { new_v2 },
+    };
+    let method_ident = &method.sig.ident;
+    let inputs = &method.sig.inputs;
+
+    let dot_await = match mode {
+        Mode::Blocking => quote! {},
+        Mode::AsyncTrait | Mode::Native | Mode::Local => quote! { .await },
+    };
+
+    let parse_response = match &method.sig.output {
+        ReturnType::Default => quote! {
+           ::jsonrpc_client::export::serde_json::from_slice::<::jsonrpc_client::Response<#return_type>>(&response)?;
+        },
+        ReturnType::Type(_, return_type) => quote_spanned! { return_type.span() =>
+            ::jsonrpc_client::export::serde_json::from_slice::<::jsonrpc_client::Response<#return_type>>(&response)?;
+        },
+    };
+    let attrs = &method.attrs;
+
+    let output = quote! {
+        ::std::result::Result<#return_type, ::jsonrpc_client::Error<Self::Error>>
+    };
+    let body = quote! {
+        let request = ::jsonrpc_client::RequestWriter::#new_request_fn(stringify!(#method_ident))
+            #(#serialized_arguments)*
+            .finish();
+
+        let response = <Self as #transport_trait_ident>::send_request(self, request)#dot_await.map_err(::jsonrpc_client::Error::Client)?;
+        let response = #parse_response
+        let success = ::std::result::Result::from(response.payload)?;
+
+        Ok(success)
+    };
+
+    // patterns like `mut` are not allowed in methods without a body
+    let mut declared_inputs = inputs.clone();
+    for input in declared_inputs.iter_mut() {
+        if let FnArg::Typed(arg) = input {
+            if let Pat::Ident(ident) = &mut *arg.pat {
+                ident.mutability = None;
+            }
         }
-    }.into())
+    }
+
+    match mode {
+        Mode::AsyncTrait | Mode::Local => (
+            quote! {
+                #(#attrs)*
+                async fn #method_ident(#declared_inputs) -> #output;
+            },
+            quote! {
+                #(#attrs)*
+                async fn #method_ident(#inputs) -> #output {
+                    #body
+                }
+            },
+        ),
+        Mode::Native => (
+            quote! {
+                #(#attrs)*
+                fn #method_ident(#declared_inputs) -> impl ::std::future::Future<Output = #output> + Send;
+            },
+            quote! {
+                #(#attrs)*
+                fn #method_ident(#inputs) -> impl ::std::future::Future<Output = #output> + Send {
+                    async move {
+                        #body
+                    }
+                }
+            },
+        ),
+        Mode::Blocking => (
+            quote! {
+                #(#attrs)*
+                fn #method_ident(#declared_inputs) -> #output;
+            },
+            quote! {
+                #(#attrs)*
+                fn #method_ident(#inputs) -> #output {
+                    #body
+                }
+            },
+        ),
+    }
 }
 
-fn make_api_impl(item: TokenStream, attr: TokenStream) -> Result<TokenStream, Error> {
-    let mut struct_def = syn::parse::<ItemStruct>(item)?;
-    let traits_to_impl = syn::parse::<Path>(attr)?;
+fn make_api_impl(item: TokenStream, attr: TokenStream, mode: Mode) -> Result<TokenStream, Error> {
+    let traits_to_impl = parse_traits_to_impl(attr)?;
 
+    match syn::parse::<Item>(item)? {
+        Item::Struct(struct_def) => make_struct_api_impl(struct_def, traits_to_impl, mode),
+        Item::Enum(enum_def) => make_enum_api_impl(enum_def, traits_to_impl, mode),
+        item => Err(Error::new(item.span(), "expected a struct or an enum")),
+    }
+}
+
+fn make_struct_api_impl(
+    mut struct_def: ItemStruct,
+    traits_to_impl: Vec<TraitToImpl>,
+    mode: Mode,
+) -> Result<TokenStream, Error> {
     let name = &struct_def.ident;
+    let base_url_method = parse_base_url_method(&struct_def.attrs)?;
 
     if struct_def.fields.is_empty() {
         return Err(Error::new(
@@ -159,110 +434,722 @@ fn make_api_impl(item: TokenStream, attr: TokenStream) -> Result<TokenStream, Er
         ));
     }
 
-    let (client_access, client_ty) = {
-        let tagged_inner = struct_def.fields.iter().enumerate().find(|(_, field)| {
-            field
-                .attrs
-                .iter()
-                .find(|attr| attr.path.is_ident("jsonrpc_client"))
-                .filter(|attr| match attr.parse_meta() {
-                    Ok(Meta::List(list)) => match list.nested.first() {
-                        Some(NestedMeta::Meta(Meta::Path(path))) => path.is_ident("inner"),
-                        _ => false,
-                    },
-                    _ => false,
-                })
-                .is_some()
-        });
+    let (client_access, client_ty) = match find_field(&struct_def.fields, "inner") {
+        Some((index, field)) => (self_access(index, field), &field.ty),
+        None => return Err(Error::new(
+            struct_def.fields.span(),
+            "struct needs to have either a field named `inner` or one tagged with `#[jsonrpc_client(inner)]`",
+        ))
+    };
 
-        let named_inner = struct_def.fields.iter().enumerate().find(|(_, field)| {
-            field
-                .ident
-                .as_ref()
-                .map(|ident| ident == "inner")
-                .unwrap_or(false)
-        });
+    let (base_url, base_url_ty) = match (base_url_method, find_field(&struct_def.fields, "base_url")) {
+        (Some(method), _) => (BaseUrl::Method(method), None),
+        (None, Some((index, field))) => (BaseUrl::Field(self_access(index, field)), Some(&field.ty)),
+        (None, None) => return Err(Error::new(
+            struct_def.fields.span(),
+            "struct needs to have either a field named `base_url` or one tagged with `#[jsonrpc_client(base_url)]`",
+        ))
+    };
 
-        match tagged_inner.or(named_inner) {
-            Some((_, Field { ident: Some(ident), ty, .. })) => (quote! { self.#ident }, ty),
-            Some((index, Field { ident: None, ty, .. })) => {
-                let index = syn::Index::from(index);
+    let transport_trait = match mode {
+        Mode::Blocking => quote! { ::jsonrpc_client::TransportBlocking },
+        Mode::Local => quote! { ::jsonrpc_client::TransportLocal },
+        Mode::AsyncTrait | Mode::Native => quote! { ::jsonrpc_client::Transport },
+    };
+
+    // the error is bound to a parameter of the impl instead of being projected from the client type in its body,
+    // which would leak a private client type through the associated type of a public client
+    let error_ident = format_ident!("__JsonrpcClientError");
+    let mut generics = struct_def.generics.clone();
+    generics.params.push(parse_quote! { #error_ident });
+    let (impl_generics, _, _) = generics.split_for_impl();
+    let (_, ty_generics, where_clause) = struct_def.generics.split_for_impl();
+
+    let trait_impls = traits_to_impl.iter().map(|TraitToImpl { path: trait_path, url_path }| {
+        let target = base_url.target(url_path.as_ref());
+
+        let mut where_clause = where_clause
+            .cloned()
+            .unwrap_or_else(|| parse_quote! { where });
+        where_clause
+            .predicates
+            .push(parse_quote! { #client_ty: #transport_trait<Error = #error_ident> });
+        where_clause
+            .predicates
+            .push(parse_quote! { #error_ident: ::std::error::Error });
+
+        // the bounds of the trait need to hold for the impl, which is not a given if the client type is generic
+        if let Some(base_url_ty) = base_url_ty.filter(|ty| is_generic_over(ty, &struct_def.generics)) {
+            where_clause
+                .predicates
+                .push(parse_quote! { #base_url_ty: ::jsonrpc_client::Endpoint });
+        }
+        if !struct_def.generics.params.is_empty() && !matches!(mode, Mode::Blocking | Mode::Local) {
+            // API traits require `Sync` for sharing a client across the futures of its methods
+            where_clause.predicates.push(parse_quote! { Self: Sync });
 
-                (
-                    quote! { self.#index },
-                    ty
-                )
+            // the future of `send_request` holds on to the inner client while it sends to the resolved URL
+            where_clause.predicates.push(parse_quote! { #client_ty: Sync });
+        }
+
+        // point errors about the trait, like it not being generated by `api`, at its path in the attribute
+        let span = trait_path.span();
+        let transport_path = transport_trait_path(trait_path);
+
+        let send_request = match mode {
+            Mode::Blocking => quote_spanned! { span =>
+                fn send_request(&self, request: ::jsonrpc_client::export::bytes::Bytes) -> ::std::result::Result<::jsonrpc_client::export::bytes::Bytes, Self::Error> {
+                    let target = #target;
+
+                    ::jsonrpc_client::TransportBlocking::send(&#client_access, &target, request)
+                }
+            },
+            Mode::Local => quote_spanned! { span =>
+                fn send_request(&self, request: ::jsonrpc_client::export::bytes::Bytes) -> ::jsonrpc_client::LocalBoxFuture<'_, ::std::result::Result<::jsonrpc_client::export::bytes::Bytes, Self::Error>> {
+                    let inner = &#client_access;
+                    let target = #target;
+
+                    Box::pin(async move { ::jsonrpc_client::TransportLocal::send(inner, &target, request).await })
+                }
             },
-            None => return Err(Error::new(
-                struct_def.fields.span(),
-                "struct needs to have either a field named `inner` or one tagged with `#[jsonrpc_client(inner)]`",
-            ))
+            Mode::AsyncTrait | Mode::Native => quote_spanned! { span =>
+                fn send_request(&self, request: ::jsonrpc_client::export::bytes::Bytes) -> ::jsonrpc_client::BoxFuture<'_, ::std::result::Result<::jsonrpc_client::export::bytes::Bytes, Self::Error>> {
+                    let inner = &#client_access;
+                    let target = #target;
+
+                    Box::pin(async move { ::jsonrpc_client::Transport::send(inner, &target, request).await })
+                }
+            },
+        };
+
+        quote_spanned! { span =>
+            impl #impl_generics #transport_path for #name #ty_generics #where_clause {
+                type Error = #error_ident;
+
+                #send_request
+            }
         }
-    };
+    }).collect::<Vec<_>>();
 
-    let base_url_access = {
-        let tagged_inner = struct_def.fields.iter().enumerate().find(|(_, field)| {
-            field
-                .attrs
-                .iter()
-                .find(|attr| attr.path.is_ident("jsonrpc_client"))
-                .filter(|attr| match attr.parse_meta() {
-                    Ok(Meta::List(list)) => match list.nested.first() {
-                        Some(NestedMeta::Meta(Meta::Path(path))) => path.is_ident("base_url"),
-                        _ => false,
-                    },
-                    _ => false,
-                })
-                .is_some()
-        });
+    // remove all `jsonrpc_client` attributes from the struct definition, unless a stacked `implement` still needs them
+    if !has_pending_implement(&struct_def.attrs) {
+        struct_def
+            .attrs
+            .retain(|attr| !attr.path.is_ident("jsonrpc_client"));
+        strip_field_attributes(&mut struct_def.fields);
+    }
+
+    Ok(quote! {
+        #struct_def
+
+        #(#trait_impls)*
+    }
+    .into())
+}
+
+/// Implements API traits for an enum by dispatching every request to the transport of the current variant.
+///
+/// The variants may hold different transports, which is why their errors are unified into a `DynError` by sending requests through `DynTransport`.
+fn make_enum_api_impl(
+    mut enum_def: ItemEnum,
+    traits_to_impl: Vec<TraitToImpl>,
+    mode: Mode,
+) -> Result<TokenStream, Error> {
+    if matches!(mode, Mode::Blocking | Mode::Local) {
+        return Err(Error::new(
+            enum_def.enum_token.span(),
+            "enums are only supported by `#[jsonrpc_client::implement]`",
+        ));
+    }
+
+    let name = &enum_def.ident;
+    let base_url_method = parse_base_url_method(&enum_def.attrs)?;
+
+    if enum_def.variants.is_empty() {
+        return Err(Error::new(
+            enum_def.span(),
+            "enum needs to have at least one variant",
+        ));
+    }
+
+    let mut client_tys = Vec::new();
+    let mut base_url_tys = Vec::new();
+    let mut variants = Vec::new();
+
+    for variant in enum_def.variants.iter() {
+        let variant_ident = &variant.ident;
+
+        let inner = find_field(&variant.fields, "inner").ok_or_else(|| Error::new(
+            variant.span(),
+            "variant needs to have either a field named `inner` or one tagged with `#[jsonrpc_client(inner)]`",
+        ))?;
+        let (base_url, base_url_field) = match (&base_url_method, find_field(&variant.fields, "base_url")) {
+            (Some(method), _) => (BaseUrl::Method(method.clone()), None),
+            (None, Some(field)) => {
+                base_url_tys.push(&field.1.ty);
+
+                (BaseUrl::Field(quote! { *base_url }), Some(field))
+            }
+            (None, None) => return Err(Error::new(
+                variant.span(),
+                "variant needs to have either a field named `base_url` or one tagged with `#[jsonrpc_client(base_url)]`",
+            )),
+        };
+
+        client_tys.push(&inner.1.ty);
+
+        // bind the client to `inner` and the base URL to `base_url` in every arm
+        let pattern = match &variant.fields {
+            Fields::Named(_) => {
+                let inner_ident = &inner.1.ident;
+                let base_url_binding = base_url_field.map(|(_, field)| {
+                    let base_url_ident = &field.ident;
+
+                    quote! { #base_url_ident: base_url, }
+                });
 
-        let named_inner = struct_def.fields.iter().enumerate().find(|(_, field)| {
-            field
-                .ident
-                .as_ref()
-                .map(|ident| ident == "base_url")
-                .unwrap_or(false)
+                quote! { Self::#variant_ident { #inner_ident: inner, #base_url_binding .. } }
+            }
+            fields => {
+                let bindings = (0..fields.len()).map(|index| {
+                    if index == inner.0 {
+                        quote! { inner }
+                    } else if base_url_field.map(|(base_url_index, _)| base_url_index)
+                        == Some(index)
+                    {
+                        quote! { base_url }
+                    } else {
+                        quote! { _ }
+                    }
+                });
+
+                quote! { Self::#variant_ident(#(#bindings),*) }
+            }
+        };
+        variants.push((pattern, base_url));
+    }
+
+    let (impl_generics, ty_generics, where_clause) = enum_def.generics.split_for_impl();
+
+    let trait_impls = traits_to_impl.iter().map(|TraitToImpl { path: trait_path, url_path }| {
+        let mut where_clause = where_clause
+            .cloned()
+            .unwrap_or_else(|| parse_quote! { where });
+        if !enum_def.generics.params.is_empty() {
+            for client_ty in client_tys.iter() {
+                where_clause
+                    .predicates
+                    .push(parse_quote! { #client_ty: ::jsonrpc_client::DynTransport });
+            }
+        }
+        for base_url_ty in base_url_tys.iter().filter(|ty| is_generic_over(ty, &enum_def.generics)) {
+            where_clause
+                .predicates
+                .push(parse_quote! { #base_url_ty: ::jsonrpc_client::Endpoint });
+        }
+
+        let span = trait_path.span();
+        let transport_path = transport_trait_path(trait_path);
+
+        let arms = variants.iter().map(|(pattern, base_url)| {
+            let target = base_url.target(url_path.as_ref());
+
+            quote_spanned! { span =>
+                #pattern => {
+                    let target = #target;
+
+                    Box::pin(async move { ::jsonrpc_client::DynTransport::send(inner, &target, request).await })
+                }
+            }
         });
 
-        match tagged_inner.or(named_inner) {
-            Some((_, Field { ident: Some(ident),  .. })) => quote! { self.#ident },
-            Some((index, Field { ident: None,  .. })) => {
-                let index = syn::Index::from(index);
+        quote_spanned! { span =>
+            impl #impl_generics #transport_path for #name #ty_generics #where_clause {
+                type Error = ::jsonrpc_client::DynError;
+
+                fn send_request(&self, request: ::jsonrpc_client::export::bytes::Bytes) -> ::jsonrpc_client::BoxFuture<'_, ::std::result::Result<::jsonrpc_client::export::bytes::Bytes, Self::Error>> {
+                    match self {
+                        #(#arms)*
+                    }
+                }
+            }
+        }
+    }).collect::<Vec<_>>();
+
+    // remove all `jsonrpc_client` attributes from the enum definition, unless a stacked `implement` still needs them
+    if !has_pending_implement(&enum_def.attrs) {
+        enum_def
+            .attrs
+            .retain(|attr| !attr.path.is_ident("jsonrpc_client"));
+        for variant in enum_def.variants.iter_mut() {
+            strip_field_attributes(&mut variant.fields);
+        }
+    }
 
-                quote! { self.#index }
+    Ok(quote! {
+        #enum_def
+
+        #(#trait_impls)*
+    }
+    .into())
+}
+
+/// Where the URL that requests are sent to comes from.
+enum BaseUrl {
+    /// A field that implements `Endpoint` or dereferences to a type that does, like a `Url`.
+    Field(proc_macro2::TokenStream),
+    /// A method like `Self::endpoint` that returns something that dereferences to a `Url`.
+    Method(ExprPath),
+}
+
+impl BaseUrl {
+    /// The expression that resolves the URL of a request, which is evaluated anew for every request.
+    fn target(&self, url_path: Option<&LitStr>) -> proc_macro2::TokenStream {
+        let base_url = match self {
+            // called as a method to dereference fields like `&Url` or a wrapper of a `Url` on the way
+            BaseUrl::Field(access) => quote! { (#access).url() },
+            BaseUrl::Method(method) => quote! { #method(self) },
+        };
+
+        let target = match url_path {
+            None => base_url,
+            Some(url_path) => quote! { ::jsonrpc_client::join_path(&#base_url, #url_path) },
+        };
+
+        match self {
+            BaseUrl::Field(_) => quote! {
+                {
+                    use ::jsonrpc_client::Endpoint as _;
+
+                    #target
+                }
             },
-            None => return Err(Error::new(
-                struct_def.fields.span(),
-                "struct needs to have either a field named `base_url` or one tagged with `#[jsonrpc_client(base_url)]`",
-            ))
+            BaseUrl::Method(_) => target,
         }
-    };
+    }
+}
+
+/// Whether the given type of a field mentions one of the type parameters of the client.
+///
+/// Bounds on such a type can only be checked where the client is used, hence they need to be part of the impl.
+fn is_generic_over(ty: &Type, generics: &Generics) -> bool {
+    fn mentions(tokens: proc_macro2::TokenStream, params: &[&Ident]) -> bool {
+        tokens.into_iter().any(|token| match token {
+            proc_macro2::TokenTree::Ident(ident) => params.contains(&&ident),
+            proc_macro2::TokenTree::Group(group) => mentions(group.stream(), params),
+            _ => false,
+        })
+    }
+
+    let params = generics
+        .type_params()
+        .map(|param| &param.ident)
+        .collect::<Vec<_>>();
+
+    mentions(ty.to_token_stream(), &params)
+}
 
-    let trait_impl = quote! {
-        #[::jsonrpc_client::export::async_trait::async_trait]
-        impl #traits_to_impl<#client_ty> for #name {
-            async fn send_request<P: ::jsonrpc_client::export::serde::de::DeserializeOwned>(&self, request: String) -> std::result::Result<::jsonrpc_client::Response<P>, <#client_ty as ::jsonrpc_client::SendRequest>::Error> {
-                ::jsonrpc_client::SendRequest::send_request(&#client_access, #base_url_access.clone(), request).await
+/// Parses `#[jsonrpc_client(base_url = "Self::endpoint")]` on a client.
+fn parse_base_url_method(attrs: &[Attribute]) -> Result<Option<ExprPath>, Error> {
+    let mut method = None;
+
+    for attr in attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("jsonrpc_client"))
+    {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => {
+                return Err(Error::new(
+                    meta.span(),
+                    "expected `#[jsonrpc_client(base_url = \"...\")]`",
+                ))
+            }
+        };
+
+        for option in list.nested {
+            match option {
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Str(lit),
+                    ..
+                })) if path.is_ident("base_url") => method = Some(lit.parse::<ExprPath>()?),
+                option => {
+                    return Err(Error::new(
+                        option.span(),
+                        "invalid option, currently only `base_url = \"...\"` is supported",
+                    ))
+                }
             }
         }
-    };
+    }
 
-    // remove all `jsonrpc_client` attributes from the struct definition
-    let fields = match &mut struct_def.fields {
-        Fields::Named(named_fields) => named_fields.named.iter_mut(),
-        Fields::Unnamed(unnamed_fields) => unnamed_fields.unnamed.iter_mut(),
-        Fields::Unit => unreachable!("struct must not be a unit struct"),
-    };
-    for field in fields {
+    Ok(method)
+}
+
+/// Accesses the given field on `self`.
+fn self_access(index: usize, field: &Field) -> proc_macro2::TokenStream {
+    match &field.ident {
+        Some(ident) => quote! { self.#ident },
+        None => {
+            let index = syn::Index::from(index);
+
+            quote! { self.#index }
+        }
+    }
+}
+
+/// Finds the field that is tagged with `#[jsonrpc_client(<name>)]` or, failing that, the field called `<name>`.
+fn find_field<'a>(fields: &'a Fields, name: &str) -> Option<(usize, &'a Field)> {
+    let tagged = fields.iter().enumerate().find(|(_, field)| {
+        field
+            .attrs
+            .iter()
+            .find(|attr| attr.path.is_ident("jsonrpc_client"))
+            .filter(|attr| match attr.parse_meta() {
+                Ok(Meta::List(list)) => match list.nested.first() {
+                    Some(NestedMeta::Meta(Meta::Path(path))) => path.is_ident(name),
+                    _ => false,
+                },
+                _ => false,
+            })
+            .is_some()
+    });
+
+    let named = fields.iter().enumerate().find(|(_, field)| {
+        field
+            .ident
+            .as_ref()
+            .map(|ident| ident == name)
+            .unwrap_or(false)
+    });
+
+    tagged.or(named)
+}
+
+/// Whether one of the given attributes is another `#[jsonrpc_client::implement*]` that is expanded after the current one.
+///
+/// Attribute macros only see the attributes that follow them, hence any such attribute still has to parse the `jsonrpc_client` attributes.
+fn has_pending_implement(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        let segments = attr.path.segments.iter().collect::<Vec<_>>();
+
+        // `implement` may also be imported from `jsonrpc_client` and hence be named without the crate
+        let macro_name = match segments.as_slice() {
+            [macro_name] if attr.path.leading_colon.is_none() => macro_name,
+            [krate, macro_name] if krate.ident == "jsonrpc_client" => macro_name,
+            _ => return false,
+        };
+
+        matches!(
+            macro_name.ident.to_string().as_str(),
+            "implement" | "implement_blocking" | "implement_local"
+        )
+    })
+}
+
+fn strip_field_attributes(fields: &mut Fields) {
+    for field in fields.iter_mut() {
         field
             .attrs
             .retain(|attr| !attr.path.is_ident("jsonrpc_client"));
     }
+}
+
+/// An API trait listed in `#[jsonrpc_client::implement]`, together with its options.
+struct TraitToImpl {
+    path: Path,
+    /// The path that is appended to the base URL for the methods of this trait.
+    url_path: Option<LitStr>,
+}
+
+/// The path of the hidden trait that clients implement for the given API trait, as generated by `make_api_trait`.
+fn transport_trait_path(trait_path: &Path) -> Path {
+    let mut path = trait_path.clone();
+
+    if let Some(last) = path.segments.last_mut() {
+        last.ident = format_ident!("{}Transport", last.ident, span = last.ident.span());
+    }
 
-    Ok(quote! {
-        #struct_def
+    path
+}
+
+/// Parses a list of API traits like `Chain, Wallet(path = "wallet/default")`.
+fn parse_traits_to_impl(attr: TokenStream) -> Result<Vec<TraitToImpl>, Error> {
+    let traits = Punctuated::<NestedMeta, Token![,]>::parse_terminated.parse(attr)?;
 
-        #trait_impl
+    if traits.is_empty() {
+        return Err(Error::new(
+            proc_macro2::Span::call_site(),
+            "expected at least one API trait to implement",
+        ));
+    }
+
+    let traits = traits
+        .into_iter()
+        .map(|item| match item {
+            NestedMeta::Meta(Meta::Path(path)) => Ok(TraitToImpl {
+                path,
+                url_path: None,
+            }),
+            NestedMeta::Meta(Meta::List(list)) => {
+                let mut url_path = None;
+
+                for option in list.nested {
+                    match option {
+                        NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                            path,
+                            lit: Lit::Str(lit),
+                            ..
+                        })) if path.is_ident("path") => url_path = Some(lit),
+                        option => {
+                            return Err(Error::new(
+                                option.span(),
+                                "invalid option, currently only `path = \"...\"` is supported",
+                            ))
+                        }
+                    }
+                }
+
+                Ok(TraitToImpl {
+                    path: list.path,
+                    url_path,
+                })
+            }
+            item => Err(Error::new(
+                item.span(),
+                "expected the path of a trait generated by `#[jsonrpc_client::api]`",
+            )),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // the hidden trait of these would fail to resolve, which keeps rustc from reporting anything more helpful
+    if let Some(TraitToImpl { path, .. }) =
+        traits.iter().find(|to_impl| is_std_trait(&to_impl.path))
+    {
+        return Err(Error::new_spanned(
+            path,
+            format!(
+                "`{}` is not a trait generated by `#[jsonrpc_client::api]`",
+                quote! { #path }.to_string().replace(' ', "")
+            ),
+        ));
+    }
+
+    Ok(traits)
+}
+
+/// Traits of the standard library that are commonly referred to by their name alone.
+const STD_TRAITS: &[&str] = &[
+    "AsMut",
+    "AsRef",
+    "Clone",
+    "Copy",
+    "Debug",
+    "Default",
+    "Display",
+    "DoubleEndedIterator",
+    "Drop",
+    "Eq",
+    "Error",
+    "ExactSizeIterator",
+    "Extend",
+    "Fn",
+    "FnMut",
+    "FnOnce",
+    "From",
+    "FromIterator",
+    "Future",
+    "Hash",
+    "Into",
+    "IntoIterator",
+    "Iterator",
+    "Ord",
+    "PartialEq",
+    "PartialOrd",
+    "Send",
+    "Sized",
+    "Sync",
+    "ToOwned",
+    "ToString",
+    "TryFrom",
+    "TryInto",
+    "Unpin",
+];
+
+/// Whether the given path points to a trait of the standard library, which can't have been generated by `api`.
+fn is_std_trait(path: &Path) -> bool {
+    match path.segments.first() {
+        Some(first) if path.segments.len() > 1 => ["std", "core", "alloc"]
+            .iter()
+            .any(|krate| first.ident == krate),
+        Some(only) => {
+            path.leading_colon.is_none() && STD_TRAITS.iter().any(|name| only.ident == name)
+        }
+        None => false,
+    }
+}
+
+fn make_notification_dispatcher(
+    input: TokenStream,
+    attr: TokenStream,
+) -> Result<TokenStream, Error> {
+    syn::parse::<Nothing>(attr)?;
+    let mut trait_def = syn::parse::<ItemTrait>(input)?;
+
+    let mut method_names = HashSet::new();
+    let mut match_arms = Vec::new();
+
+    for item in trait_def.items.iter_mut() {
+        let method = match item {
+            TraitItem::Method(method) => method,
+            _ => return Err(Error::new(item.span(), "trait must only define methods")),
+        };
+
+        if method.sig.asyncness.is_some() {
+            return Err(Error::new(
+                method.sig.span(),
+                "notification handlers must not be async",
+            ));
+        }
+
+        match method.sig.inputs.first() {
+            Some(FnArg::Receiver(Receiver {
+                reference: Some(_),
+                mutability: None,
+                ..
+            })) => {}
+            Some(receiver @ FnArg::Receiver(_)) => {
+                return Err(Error::new(
+                    receiver.span(),
+                    "notification handlers must take `&self`",
+                ))
+            }
+            _ => {
+                return Err(Error::new(
+                    method.sig.span(),
+                    "notification handlers must take `&self`",
+                ))
+            }
+        }
+
+        if let ReturnType::Type(_, return_type) = &method.sig.output {
+            return Err(Error::new(
+                return_type.span(),
+                "notification handlers must not return a value",
+            ));
+        }
+
+        let params_ty = match method.sig.inputs.iter().skip(1).collect::<Vec<_>>().as_slice() {
+            [] => None,
+            [FnArg::Typed(params)] => Some(&params.ty),
+            [_, extra, ..] | [extra] => {
+                return Err(Error::new(
+                    extra.span(),
+                    "notification handlers must take at most one argument for the params of the notification",
+                ))
+            }
+        };
+
+        let method_ident = &method.sig.ident;
+        let method_name = match take_method_name(&mut method.attrs)? {
+            Some(method_name) => method_name,
+            None => {
+                let ident = method_ident.to_string();
+
+                ident.strip_prefix("on_").unwrap_or(&ident).to_owned()
+            }
+        };
+
+        if !method_names.insert(method_name.clone()) {
+            return Err(Error::new(
+                method.sig.span(),
+                format!("notification `{}` is handled more than once", method_name),
+            ));
+        }
+
+        let handler_call = match params_ty {
+            Some(params_ty) => quote_spanned! { params_ty.span() =>
+                let params = ::jsonrpc_client::export::serde_json::from_value::<#params_ty>(params.unwrap_or(::jsonrpc_client::export::serde_json::Value::Null))
+                    .map_err(|source| ::jsonrpc_client::DispatchError::InvalidParams {
+                        method: method.clone(),
+                        source,
+                    })?;
+
+                self.#method_ident(params);
+            },
+            None => quote! {
+                self.#method_ident();
+            },
+        };
+
+        match_arms.push(quote! {
+            #method_name => {
+                #handler_call
+
+                Ok(())
+            }
+        });
+    }
+
+    let dispatch_method: TraitItemMethod = syn::parse_quote! {
+        /// Deserializes the params of the given notification and invokes the handler for its method.
+        fn dispatch_notification(&self, notification: ::jsonrpc_client::Notification) -> std::result::Result<(), ::jsonrpc_client::DispatchError> {
+            #[allow(unused_variables)]
+            let ::jsonrpc_client::Notification { method, params, .. } = notification;
+
+            match method.as_str() {
+                #(#match_arms)*
+                _ => Err(::jsonrpc_client::DispatchError::UnknownMethod(method)),
+            }
+        }
+    };
+    trait_def.items.push(TraitItem::Method(dispatch_method));
+
+    Ok(quote! {
+        #trait_def
     }
     .into())
 }
+
+/// Removes the `#[jsonrpc_client(method = "...")]` attribute from the given attributes and returns the configured method name.
+fn take_method_name(attrs: &mut Vec<Attribute>) -> Result<Option<String>, Error> {
+    let mut method_name = None;
+
+    for attr in attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("jsonrpc_client"))
+    {
+        let name_value = match attr.parse_meta()? {
+            Meta::List(list) => match list.nested.first() {
+                Some(NestedMeta::Meta(Meta::NameValue(name_value)))
+                    if list.nested.len() == 1 && name_value.path.is_ident("method") =>
+                {
+                    Some(name_value.clone())
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+        .ok_or_else(|| {
+            Error::new(
+                attr.span(),
+                r#"expected `#[jsonrpc_client(method = "...")]`"#,
+            )
+        })?;
+
+        match name_value.lit {
+            Lit::Str(str_lit) => method_name = Some(str_lit.value()),
+            other => {
+                return Err(Error::new(
+                    other.span(),
+                    "argument to `method` must be a string",
+                ))
+            }
+        }
+    }
+
+    attrs.retain(|attr| !attr.path.is_ident("jsonrpc_client"));
+
+    Ok(method_name)
+}